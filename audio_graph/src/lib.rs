@@ -4,11 +4,13 @@ mod audio_graph;
 mod audio_graph_node;
 mod audio_graph_node_impl;
 mod mixer_node;
+mod smoothed_gain;
 
 pub use audio_graph::AudioGraph;
 pub use audio_graph_node::AudioGraphNode;
 pub use audio_graph_node_impl::AudioGraphNodeImpl;
 pub use mixer_node::MixerNode;
+pub use smoothed_gain::SmoothedGain;
 
 #[must_use]
 pub fn pan(angle: f32) -> (f32, f32) {
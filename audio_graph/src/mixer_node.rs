@@ -1,4 +1,4 @@
-use crate::{pan, AudioGraphNodeImpl};
+use crate::{AudioGraphNodeImpl, SmoothedGain};
 use atomig::Atomic;
 use std::sync::{
     atomic::{AtomicUsize, Ordering::SeqCst},
@@ -11,6 +11,7 @@ pub struct MixerNode {
     pub volume: Atomic<f32>,
     /// -1 <= pan <= 1
     pub pan: Atomic<f32>,
+    gain: SmoothedGain,
     buf: Mutex<Vec<f32>>,
     last_sample: AtomicUsize,
 }
@@ -21,14 +22,12 @@ impl AudioGraphNodeImpl for MixerNode {
 
         // we can assume the buffer size doesn't vary for the same buf_start_sample
         if buf_start_sample != self.last_sample.swap(buf_start_sample, SeqCst) {
-            let volume = self.volume.load(SeqCst);
-            let (mut lpan, mut rpan) = pan(self.pan.load(SeqCst));
-            lpan *= volume;
-            rpan *= volume;
-
-            buf.iter_mut()
-                .enumerate()
-                .for_each(|(i, s)| *s *= if i % 2 == 0 { lpan } else { rpan });
+            self.gain.apply(
+                buf_start_sample,
+                self.volume.load(SeqCst),
+                self.pan.load(SeqCst),
+                buf,
+            );
 
             node_buf.clear();
             node_buf.extend(buf.iter().copied());
@@ -46,6 +45,7 @@ impl Default for MixerNode {
         Self {
             volume: Atomic::new(1.0),
             pan: Atomic::default(),
+            gain: SmoothedGain::default(),
             buf: Mutex::default(),
             last_sample: AtomicUsize::new(usize::MAX),
         }
@@ -0,0 +1,66 @@
+use crate::pan;
+use std::sync::Mutex;
+
+/// how far the gain moves towards its target every frame
+const SMOOTHING: f32 = 0.005;
+
+/// applies volume and pan to interleaved stereo audio, smoothing out sudden changes to avoid
+/// zipper noise
+#[derive(Debug, Default)]
+pub struct SmoothedGain {
+    /// the ramp applied to the last buffer, or `None` before the first buffer
+    last: Mutex<Option<Ramp>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Ramp {
+    /// the start sample of the buffer
+    sample: usize,
+    /// the gain of each channel before the first frame
+    start: [f32; 2],
+    /// the gain of each channel after the last frame
+    end: [f32; 2],
+}
+
+impl SmoothedGain {
+    /// the audio graph can render a node more than once for the same buffer, so calling this
+    /// again with the same `buf_start_sample` applies the same ramp instead of advancing it
+    pub fn apply(&self, buf_start_sample: usize, volume: f32, angle: f32, buf: &mut [f32]) {
+        let (lpan, rpan) = pan(angle);
+        let target = [lpan * volume, rpan * volume];
+
+        let mut last = self.last.lock().unwrap();
+        let start = match *last {
+            Some(ramp) if ramp.sample == buf_start_sample => ramp.start,
+            Some(ramp) => ramp.end,
+            None => target,
+        };
+
+        let mut gain = start;
+        buf.chunks_exact_mut(2).for_each(|frame| {
+            gain[0] = (target[0] - gain[0]).mul_add(SMOOTHING, gain[0]);
+            gain[1] = (target[1] - gain[1]).mul_add(SMOOTHING, gain[1]);
+
+            frame[0] *= gain[0];
+            frame[1] *= gain[1];
+        });
+
+        *last = Some(Ramp {
+            sample: buf_start_sample,
+            start,
+            end: gain,
+        });
+    }
+
+    /// applies volume and pan without smoothing, and without touching the smoothing state
+    ///
+    /// used for offline rendering, which mustn't disturb the ramp of the live output
+    pub fn snap(volume: f32, angle: f32, buf: &mut [f32]) {
+        let (lpan, rpan) = pan(angle);
+
+        buf.chunks_exact_mut(2).for_each(|frame| {
+            frame[0] *= lpan * volume;
+            frame[1] *= rpan * volume;
+        });
+    }
+}
@@ -24,10 +24,17 @@ pub struct GuiExt {
 }
 
 impl GuiExt {
-    pub fn new(plugin_gui: PluginGui, instance: &mut PluginMainThreadHandle<'_>) -> Self {
+    pub fn new(
+        plugin_gui: PluginGui,
+        instance: &mut PluginMainThreadHandle<'_>,
+        window_handle: RawWindowHandle,
+    ) -> Self {
+        // wayland has no way to embed a foreign window, so plugins have to float there
+        let embeddable = !matches!(window_handle, RawWindowHandle::Wayland(..));
+
         Self {
             plugin_gui,
-            configuration: Self::negotiate_configuration(&plugin_gui, instance),
+            configuration: Self::negotiate_configuration(&plugin_gui, instance, embeddable),
             is_open: false,
             is_resizeable: false,
         }
@@ -36,6 +43,7 @@ impl GuiExt {
     fn negotiate_configuration(
         gui: &PluginGui,
         plugin: &mut PluginMainThreadHandle<'_>,
+        embeddable: bool,
     ) -> Option<GuiConfiguration<'static>> {
         let api_type = GuiApiType::default_for_current_platform()?;
         let mut config = GuiConfiguration {
@@ -43,7 +51,7 @@ impl GuiExt {
             is_floating: false,
         };
 
-        if gui.is_api_supported(plugin, config) {
+        if embeddable && gui.is_api_supported(plugin, config) {
             Some(config)
         } else {
             config.is_floating = true;
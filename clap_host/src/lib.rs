@@ -132,7 +132,7 @@ pub fn open_gui(
 
     let mut gui = instance
         .access_handler(|h| h.gui)
-        .map(|gui| GuiExt::new(gui, &mut instance.plugin_handle()))
+        .map(|gui| GuiExt::new(gui, &mut instance.plugin_handle(), window_handle))
         .unwrap();
 
     if gui.needs_floating().unwrap() {
@@ -0,0 +1,29 @@
+//! renders an audio file through an arrangement into a wav file, without opening an audio device
+//!
+//! `cargo run --example render -- <input> <output.wav>`
+
+#![expect(unused_crate_dependencies)]
+
+use generic_daw_core::{ArrangementBuilder, ExportOptions, InterleavedAudio, ResampleQuality};
+use std::{
+    env::args,
+    io::{stdout, Write as _},
+    path::PathBuf,
+};
+
+fn main() {
+    let mut args = args().skip(1);
+    let input = PathBuf::from(args.next().expect("missing input path"));
+    let output = PathBuf::from(args.next().expect("missing output path"));
+
+    let arrangement = ArrangementBuilder::default().bpm(120).build();
+
+    let audio = InterleavedAudio::create(input, &arrangement.meter, ResampleQuality::High).unwrap();
+    arrangement.add_audio_track(audio);
+
+    let report = arrangement
+        .export(&output, ExportOptions::default())
+        .unwrap();
+
+    writeln!(stdout(), "{}: {report}", output.display()).unwrap();
+}
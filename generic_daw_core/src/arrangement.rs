@@ -1,11 +1,15 @@
-use crate::{LiveSample, Meter, Position, Track};
+use crate::{
+    export::{LoudnessMeter, SampleWriter},
+    AudioClip, AudioTrack, ExportOptions, InterleavedAudio, LiveSample, LoudnessReport, Meter,
+    Normalization, Position, Signal, SignalGenerator, Tap, Track, TrackClip,
+};
+use anyhow::Result;
 use audio_graph::{AudioGraph, AudioGraphNodeImpl};
-use hound::WavWriter;
 use std::{
     path::Path,
     sync::{
         atomic::{AtomicBool, Ordering::SeqCst},
-        Arc, OnceLock, RwLock,
+        Arc, Mutex, OnceLock, RwLock,
     },
 };
 
@@ -20,6 +24,13 @@ pub struct Arrangement {
     pub live_sample_playback: RwLock<Vec<LiveSample>>,
     /// whether the metronome is currently enabled
     pub metronome: AtomicBool,
+    /// the most recent live output, for the stereo meters
+    pub tap: Tap,
+    /// the test tone mixed into the master output, if one is playing
+    ///
+    /// this is kept out of the audio graph, since the graph caches its output by sample position,
+    /// which doesn't advance while the arrangement is stopped
+    signal_generator: Mutex<Option<SignalGenerator>>,
     pub(crate) on_bar_click: OnceLock<Arc<[f32]>>,
     pub(crate) off_bar_click: OnceLock<Arc<[f32]>>,
 }
@@ -67,6 +78,12 @@ impl AudioGraphNodeImpl for Arrangement {
                 .write()
                 .unwrap()
                 .retain(|sample| !sample.over());
+
+            if let Some(signal_generator) = &*self.signal_generator.lock().unwrap() {
+                signal_generator.mix_into(buf);
+            }
+
+            self.tap.push(buf);
         }
     }
 }
@@ -77,6 +94,65 @@ impl Arrangement {
         Arc::new(Self::default())
     }
 
+    /// whether the arrangement is currently being played back
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        self.meter.playing.load(SeqCst)
+    }
+
+    /// starts playback from the current position
+    pub fn play(&self) {
+        self.meter.playing.store(true, SeqCst);
+    }
+
+    /// stops playback, keeping the current position
+    pub fn pause(&self) {
+        self.meter.playing.store(false, SeqCst);
+    }
+
+    /// stops playback and moves back to the start of the arrangement
+    pub fn stop(&self) {
+        self.pause();
+        self.seek(Position::default());
+    }
+
+    /// moves the playhead to `position`, cutting off anything that's being played back live
+    pub fn seek(&self, position: Position) {
+        self.seek_to_sample(position.in_interleaved_samples(&self.meter));
+    }
+
+    /// moves the playhead to `sample`, cutting off anything that's being played back live
+    pub fn seek_to_sample(&self, sample: usize) {
+        self.meter.sample.store(sample, SeqCst);
+        self.live_sample_playback.write().unwrap().clear();
+    }
+
+    /// adds a new audio track at the bottom of the arrangement, with `audio` at its start
+    pub fn add_audio_track(&self, audio: Arc<InterleavedAudio>) -> Arc<Track> {
+        let node = AudioTrack::create(self.meter.clone());
+
+        let added = self.audio_graph.add(node.clone().into());
+        debug_assert!(added);
+        let connected = self
+            .audio_graph
+            .connect(&self.audio_graph.root(), &node.clone().into());
+        debug_assert!(connected);
+
+        let track = node.downcast_arc::<Track>().unwrap();
+        let pushed = track.try_push(&AudioClip::create(audio, self.meter.clone()));
+        debug_assert!(pushed);
+
+        self.tracks.write().unwrap().push(track.clone());
+
+        track
+    }
+
+    /// replaces the test signal generator with one playing `signal`, or removes it
+    pub fn set_signal_generator(&self, signal: Option<Signal>) {
+        *self.signal_generator.lock().unwrap() =
+            signal.map(|signal| SignalGenerator::create(signal, self.meter.clone()));
+    }
+
     #[must_use]
     pub fn len(&self) -> Position {
         self.tracks
@@ -88,37 +164,186 @@ impl Arrangement {
             .unwrap_or_else(Position::default)
     }
 
-    pub fn export(&self, path: &Path) {
+    /// plays back `audio` once, independently of the arrangement
+    pub fn audition(&self, audio: &InterleavedAudio) {
+        self.live_sample_playback
+            .write()
+            .unwrap()
+            .push(LiveSample::new(audio.samples.clone(), 0));
+    }
+
+    pub fn export(&self, path: &Path, options: ExportOptions) -> Result<LoudnessReport> {
+        let _prepared = self.prepare_export()?;
+
+        self.render(path, options, |buf_start_sample, buf| {
+            self.fill_buf(buf_start_sample, buf);
+        })
+    }
+
+    /// exports every track to its own file, next to `path`
+    ///
+    /// stems are rendered post-fader, and are named after `template`, where `{name}` is replaced
+    /// with the file name of `path` and `{track}` with the track number, keeping the extension of
+    /// `path`
+    ///
+    /// stems are never normalized, so that they still add up to the full mix
+    ///
+    /// `on_stem` is called with the index of each stem and the number of stems, before it's
+    /// rendered
+    pub fn export_stems(
+        &self,
+        path: &Path,
+        template: &str,
+        options: ExportOptions,
+        mut on_stem: impl FnMut(usize, usize),
+    ) -> Result<()> {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = path.extension().unwrap_or("wav".as_ref()).to_string_lossy();
+        let options = ExportOptions {
+            normalization: Normalization::None,
+            ..options
+        };
+
+        let _prepared = self.prepare_export()?;
+
+        let tracks = self.tracks.read().unwrap();
+        for (i, track) in tracks.iter().enumerate() {
+            on_stem(i, tracks.len());
+
+            let stem = template
+                .replace("{name}", &name)
+                .replace("{track}", &(i + 1).to_string());
+            let path = path.with_file_name(format!("{stem}.{extension}"));
+
+            self.render(&path, options, |buf_start_sample, buf| {
+                buf.fill(0.0);
+                track.fill_buf(buf_start_sample, buf);
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// resamples every sample that was loaded with fast resampling at high quality, until the
+    /// returned guard is dropped
+    fn prepare_export(&self) -> Result<Prepared<'_>> {
+        let prepared = Prepared(self);
+
+        for audio in self.audio() {
+            audio.prepare_export(&self.meter)?;
+        }
+
+        Ok(prepared)
+    }
+
+    /// the audio of every audio clip in the arrangement
+    fn audio(&self) -> Vec<Arc<InterleavedAudio>> {
+        self.tracks
+            .read()
+            .unwrap()
+            .iter()
+            .flat_map(|track| {
+                track
+                    .clips()
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|clip| match &**clip {
+                        TrackClip::Audio(clip) => Some(clip.audio.clone()),
+                        TrackClip::Midi(..) => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// renders the arrangement with `fill_buf` into a file at `path`
+    ///
+    /// the render is only buffered in memory when it's normalized, since the gain isn't known
+    /// until all of it is measured
+    fn render(
+        &self,
+        path: &Path,
+        options: ExportOptions,
+        mut fill_buf: impl FnMut(usize, &mut [f32]),
+    ) -> Result<LoudnessReport> {
         const CHUNK_SIZE: usize = 16;
 
-        self.meter.playing.store(false, SeqCst);
-        self.meter.exporting.store(true, SeqCst);
-
-        let mut writer = WavWriter::create(
-            path,
-            hound::WavSpec {
-                channels: 2,
-                sample_rate: self.meter.sample_rate.load(SeqCst),
-                bits_per_sample: 32,
-                sample_format: hound::SampleFormat::Float,
-            },
-        )
-        .unwrap();
-
-        let mut buf = [0.0; CHUNK_SIZE];
-        (0..self.len().in_interleaved_samples(&self.meter))
-            .step_by(CHUNK_SIZE)
-            .for_each(|i| {
-                self.fill_buf(i, &mut buf);
-
-                for s in buf {
-                    writer.write_sample(s).unwrap();
-                }
-            });
-
-        writer.flush().unwrap();
-
-        self.meter.exporting.store(false, SeqCst);
-        self.live_sample_playback.write().unwrap().clear();
+        let exporting = Exporting::start(self);
+
+        let sample_rate = self.meter.sample_rate.load(SeqCst);
+        let len = self.len().in_interleaved_samples(&self.meter);
+        let mut meter = LoudnessMeter::new(sample_rate);
+
+        let chunks = (0..len).step_by(CHUNK_SIZE).map(|start| {
+            let mut buf = [0.0; CHUNK_SIZE];
+            fill_buf(start, &mut buf);
+            buf
+        });
+
+        if options.normalization == Normalization::None {
+            let mut writer = SampleWriter::create(path, sample_rate, options)?;
+
+            for s in chunks.flatten() {
+                meter.push(s);
+                writer.write(s)?;
+            }
+
+            let mut report = LoudnessReport::measure(&meter, options);
+            report.clipped = writer.finalize()?;
+
+            return Ok(report);
+        }
+
+        let samples = chunks
+            .flatten()
+            .inspect(|&s| meter.push(s))
+            .collect::<Vec<_>>();
+
+        drop(exporting);
+
+        let mut report = LoudnessReport::measure(&meter, options);
+        let gain = 10f32.powf(report.gain / 20.0);
+
+        let mut writer = SampleWriter::create(path, sample_rate, options)?;
+
+        for s in samples {
+            writer.write(s * gain)?;
+        }
+
+        report.clipped = writer.finalize()?;
+
+        Ok(report)
+    }
+}
+
+/// keeps the high quality samples of an export around for as long as it's alive
+struct Prepared<'a>(&'a Arrangement);
+
+impl Drop for Prepared<'_> {
+    fn drop(&mut self) {
+        for audio in self.0.audio() {
+            audio.finish_export();
+        }
+    }
+}
+
+/// keeps the arrangement in export mode for as long as it's alive, so that it's left again even
+/// when an export fails
+struct Exporting<'a>(&'a Arrangement);
+
+impl<'a> Exporting<'a> {
+    fn start(arrangement: &'a Arrangement) -> Self {
+        arrangement.meter.playing.store(false, SeqCst);
+        arrangement.meter.exporting.store(true, SeqCst);
+
+        Self(arrangement)
+    }
+}
+
+impl Drop for Exporting<'_> {
+    fn drop(&mut self) {
+        self.0.meter.exporting.store(false, SeqCst);
+        self.0.live_sample_playback.write().unwrap().clear();
     }
 }
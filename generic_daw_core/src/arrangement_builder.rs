@@ -0,0 +1,65 @@
+use crate::{Arrangement, Denominator, Numerator};
+use std::sync::{atomic::Ordering::SeqCst, Arc};
+
+/// builds an [`Arrangement`] with a given tempo, time signature and sample rate
+///
+/// the sample rate is overwritten by [`build_output_stream`](crate::build_output_stream), so it
+/// only matters for arrangements that are rendered offline
+#[derive(Clone, Copy, Debug)]
+pub struct ArrangementBuilder {
+    bpm: u16,
+    numerator: Numerator,
+    denominator: Denominator,
+    sample_rate: u32,
+}
+
+impl Default for ArrangementBuilder {
+    fn default() -> Self {
+        Self {
+            bpm: 140,
+            numerator: Numerator::default(),
+            denominator: Denominator::default(),
+            sample_rate: 44100,
+        }
+    }
+}
+
+impl ArrangementBuilder {
+    /// the tempo, clamped between 30 and 600 BPM
+    #[must_use]
+    pub fn bpm(mut self, bpm: u16) -> Self {
+        self.bpm = bpm.clamp(30, 600);
+        self
+    }
+
+    #[must_use]
+    pub fn time_signature(mut self, numerator: Numerator, denominator: Denominator) -> Self {
+        self.numerator = numerator;
+        self.denominator = denominator;
+        self
+    }
+
+    #[must_use]
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Arc<Arrangement> {
+        let arrangement = Arrangement::create();
+
+        arrangement.meter.bpm.store(self.bpm, SeqCst);
+        arrangement.meter.numerator.store(self.numerator, SeqCst);
+        arrangement
+            .meter
+            .denominator
+            .store(self.denominator, SeqCst);
+        arrangement
+            .meter
+            .sample_rate
+            .store(self.sample_rate, SeqCst);
+
+        arrangement
+    }
+}
@@ -0,0 +1,366 @@
+use anyhow::Result;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use std::{fmt::Display, fs::File, io::BufWriter, path::Path};
+use strum::VariantArray;
+
+mod loudness;
+
+pub(crate) use loudness::LoudnessMeter;
+
+/// the highest sample peak loudness normalization may raise a render to, in dBFS
+const PEAK_CEILING: f32 = -1.0;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum BitDepth {
+    Int16,
+    Int24,
+    Int32,
+    #[default]
+    Float32,
+}
+
+impl Display for BitDepth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Int16 => "16-bit",
+            Self::Int24 => "24-bit",
+            Self::Int32 => "32-bit",
+            Self::Float32 => "32-bit float",
+        })
+    }
+}
+
+impl BitDepth {
+    const fn bits(self) -> u16 {
+        match self {
+            Self::Int16 => 16,
+            Self::Int24 => 24,
+            Self::Int32 | Self::Float32 => 32,
+        }
+    }
+
+    /// the largest value an integer sample can hold, or `None` for floating point samples
+    fn max_value(self) -> Option<f64> {
+        match self {
+            Self::Float32 => None,
+            _ => Some(f64::from((1u32 << (self.bits() - 1)) - 1)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum Dithering {
+    None,
+    #[default]
+    Triangular,
+    NoiseShaped,
+}
+
+impl Display for Dithering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "No Dither",
+            Self::Triangular => "TPDF",
+            Self::NoiseShaped => "TPDF + Noise Shaping",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum Normalization {
+    #[default]
+    None,
+    Peak,
+    Loudness,
+}
+
+impl Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "No Normalization",
+            Self::Peak => "Peak",
+            Self::Loudness => "Loudness",
+        })
+    }
+}
+
+impl Normalization {
+    /// the default target, in dBFS for `Peak` and in LUFS for `Loudness`
+    #[must_use]
+    pub const fn default_target(self) -> f32 {
+        match self {
+            Self::None => 0.0,
+            Self::Peak => -1.0,
+            Self::Loudness => -14.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExportOptions {
+    pub bit_depth: BitDepth,
+    /// only applied when exporting to an integer bit depth
+    pub dithering: Dithering,
+    pub normalization: Normalization,
+    /// in dBFS when normalizing to a peak, in LUFS when normalizing to a loudness
+    pub normalization_target: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoudnessReport {
+    /// the sample peak of the render, in dBFS
+    pub peak: f32,
+    /// the integrated loudness of the render, in LUFS
+    pub loudness: f32,
+    /// the gain applied by normalization, in dB
+    pub gain: f32,
+    /// the number of samples that clipped when converting to an integer bit depth
+    pub clipped: usize,
+}
+
+impl Display for LoudnessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.1} dBFS peak, {:.1} LUFS, {:+.1} dB gain",
+            self.peak, self.loudness, self.gain
+        )?;
+
+        if self.clipped != 0 {
+            write!(f, ", {} samples clipped", self.clipped)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl LoudnessReport {
+    /// the report of a render measured by `meter`, before anything was written
+    pub(crate) fn measure(meter: &LoudnessMeter, options: ExportOptions) -> Self {
+        let peak = meter.peak();
+        let loudness = meter.loudness();
+
+        let gain = match options.normalization {
+            Normalization::None => 0.0,
+            Normalization::Peak => options.normalization_target - peak,
+            // raising quiet but peaky renders to the target loudness would clip them
+            Normalization::Loudness => {
+                (options.normalization_target - loudness).min(PEAK_CEILING - peak)
+            }
+        };
+
+        Self {
+            peak,
+            loudness,
+            // silence can't be normalized
+            gain: if gain.is_finite() { gain } else { 0.0 },
+            clipped: 0,
+        }
+    }
+}
+
+pub(crate) struct SampleWriter {
+    writer: WavWriter<BufWriter<File>>,
+    options: ExportOptions,
+    /// state of the pseudo-random number generator used for dithering
+    rng: u32,
+    /// the last quantization error of each channel, used for noise shaping
+    error: [f64; 2],
+    /// the channel of the next sample
+    channel: usize,
+    /// the number of samples that were clipped so far
+    clipped: usize,
+}
+
+impl SampleWriter {
+    pub fn create(path: &Path, sample_rate: u32, options: ExportOptions) -> Result<Self> {
+        let writer = WavWriter::create(
+            path,
+            WavSpec {
+                channels: 2,
+                sample_rate,
+                bits_per_sample: options.bit_depth.bits(),
+                sample_format: if options.bit_depth == BitDepth::Float32 {
+                    SampleFormat::Float
+                } else {
+                    SampleFormat::Int
+                },
+            },
+        )?;
+
+        Ok(Self {
+            writer,
+            options,
+            rng: 0x9e37_79b9,
+            error: [0.0; 2],
+            channel: 0,
+            clipped: 0,
+        })
+    }
+
+    pub fn write(&mut self, sample: f32) -> Result<()> {
+        let Some(max) = self.options.bit_depth.max_value() else {
+            self.writer.write_sample(sample)?;
+            return Ok(());
+        };
+
+        let mut sample = f64::from(sample) * max;
+
+        if self.options.dithering == Dithering::NoiseShaped {
+            sample -= self.error[self.channel];
+        }
+
+        // the sum of two uniform distributions gives a triangular distribution of +-1 LSB
+        let dither = match self.options.dithering {
+            Dithering::None => 0.0,
+            Dithering::Triangular | Dithering::NoiseShaped => self.random() + self.random() - 1.0,
+        };
+
+        let rounded = (sample + dither).round();
+        let quantized = rounded.clamp(-max - 1.0, max);
+
+        if quantized != rounded {
+            self.clipped += 1;
+        }
+
+        self.error[self.channel] = quantized - sample;
+        self.channel ^= 1;
+
+        self.writer.write_sample(quantized as i32)?;
+
+        Ok(())
+    }
+
+    /// finishes the file, returning how many samples were clipped
+    pub fn finalize(self) -> Result<usize> {
+        self.writer.finalize()?;
+
+        Ok(self.clipped)
+    }
+
+    /// a uniformly distributed number in `0.0..1.0`
+    fn random(&mut self) -> f64 {
+        // xorshift32
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+
+        f64::from(self.rng) / (f64::from(u32::MAX) + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BitDepth, Dithering, ExportOptions, LoudnessMeter, LoudnessReport, Normalization,
+        SampleWriter, PEAK_CEILING,
+    };
+    use hound::WavReader;
+    use std::{env::temp_dir, f64::consts::TAU, fs::remove_file};
+
+    /// a constant signal, 0.3 LSB above 1000 LSB at 16 bits
+    const CONSTANT: f32 = 1000.3 / 32767.0;
+
+    /// writes `samples` to a 16 bit file and reads them back, along with the number of clipped
+    /// samples
+    fn write(name: &str, dithering: Dithering, samples: &[f32]) -> (Vec<i32>, usize) {
+        let path = temp_dir().join(format!("generic_daw_{name}.wav"));
+        let options = ExportOptions {
+            bit_depth: BitDepth::Int16,
+            dithering,
+            ..ExportOptions::default()
+        };
+
+        let mut writer = SampleWriter::create(&path, 44100, options).unwrap();
+        for &sample in samples {
+            writer.write(sample).unwrap();
+        }
+        let clipped = writer.finalize().unwrap();
+
+        let written = WavReader::open(&path)
+            .unwrap()
+            .samples::<i32>()
+            .map(Result::unwrap)
+            .collect();
+        remove_file(&path).unwrap();
+
+        (written, clipped)
+    }
+
+    #[test]
+    fn no_dither_rounds() {
+        let (written, _) = write("no_dither", Dithering::None, &[CONSTANT; 20000]);
+
+        assert!(written.iter().all(|&s| s == 1000));
+    }
+
+    #[test]
+    fn triangular_dither_is_unbiased() {
+        let (written, _) = write(
+            "triangular_dither",
+            Dithering::Triangular,
+            &[CONSTANT; 20000],
+        );
+
+        // the dither is at most one LSB in either direction
+        assert!(written.iter().all(|s| (999..=1001).contains(s)));
+
+        let input = f64::from(CONSTANT) * 32767.0;
+        let mean = f64::from(written.iter().sum::<i32>()) / written.len() as f64;
+        assert!((mean - input).abs() < 0.05, "{mean}");
+    }
+
+    #[test]
+    fn noise_shaping_feeds_back_the_error() {
+        let (written, _) = write(
+            "noise_shaped_dither",
+            Dithering::NoiseShaped,
+            &[CONSTANT; 20000],
+        );
+        let input = f64::from(CONSTANT) * 32767.0;
+
+        // the error of every sample is its own quantization error minus the one before it, so the
+        // total error of a channel is just the last quantization error
+        for channel in 0..2 {
+            let error = written
+                .iter()
+                .skip(channel)
+                .step_by(2)
+                .map(|&s| f64::from(s) - input)
+                .sum::<f64>();
+
+            assert!(error.abs() <= 1.5, "{error}");
+        }
+    }
+
+    #[test]
+    fn counts_clipped_samples() {
+        let (written, clipped) = write("clipping", Dithering::None, &[1.5, -1.5, 1.0, -1.0]);
+
+        assert_eq!(written, [32767, -32768, 32767, -32767]);
+        assert_eq!(clipped, 2);
+    }
+
+    #[test]
+    fn loudness_normalization_stays_below_the_peak_ceiling() {
+        // a quiet sine with a single loud spike, which would clip if raised to the target loudness
+        let mut meter = LoudnessMeter::new(48000);
+        for i in 0..48000 * 5 * 2 {
+            meter.push(if i == 1000 {
+                0.5
+            } else {
+                (0.01 * (TAU * 997.0 * f64::from(i / 2) / 48000.0).sin()) as f32
+            });
+        }
+
+        let options = ExportOptions {
+            normalization: Normalization::Loudness,
+            normalization_target: -14.0,
+            ..ExportOptions::default()
+        };
+        let report = LoudnessReport::measure(&meter, options);
+
+        assert!(report.gain < options.normalization_target - report.loudness);
+        assert!((report.peak + report.gain - PEAK_CEILING).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,227 @@
+use std::f64::consts::PI;
+
+/// blocks quieter than this, in LUFS, are never counted towards the integrated loudness
+const ABSOLUTE_GATE: f64 = -70.0;
+
+/// a biquad filter in direct form I
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0].mul_add(
+            x,
+            self.b[1].mul_add(
+                self.x[0],
+                self.b[2].mul_add(
+                    self.x[1],
+                    (-self.a[0]).mul_add(self.y[0], -self.a[1] * self.y[1]),
+                ),
+            ),
+        );
+
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+
+        y
+    }
+}
+
+/// the K-weighting filter from ITU-R BS.1770, for a single channel
+struct KWeighting([Biquad; 2]);
+
+impl KWeighting {
+    fn new(sample_rate: u32) -> Self {
+        let sample_rate = f64::from(sample_rate);
+
+        // high shelf, modelling the acoustic effects of the head
+        let k = (PI * 1_681.974_450_955_533 / sample_rate).tan();
+        let kk = k * k;
+        let q = 0.707_175_236_955_419_6;
+        let vh = 10f64.powf(3.999_843_853_973_347 / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + kk;
+        let shelf = Biquad::new(
+            [
+                (vh + vb * k / q + kk) / a0,
+                2.0 * (kk - vh) / a0,
+                (vh - vb * k / q + kk) / a0,
+            ],
+            [2.0 * (kk - 1.0) / a0, (1.0 - k / q + kk) / a0],
+        );
+
+        // high pass, the "RLB" weighting curve
+        let k = (PI * 38.135_470_876_024_44 / sample_rate).tan();
+        let kk = k * k;
+        let q = 0.500_327_037_323_877_3;
+        let a0 = 1.0 + k / q + kk;
+        let high_pass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (kk - 1.0) / a0, (1.0 - k / q + kk) / a0],
+        );
+
+        Self([shelf, high_pass])
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        self.0[1].process(self.0[0].process(x))
+    }
+}
+
+/// measures the sample peak and the integrated loudness of interleaved stereo audio, as defined in
+/// ITU-R BS.1770, one sample at a time
+pub struct LoudnessMeter {
+    filters: [KWeighting; 2],
+    /// the number of samples in a 100ms step
+    ///
+    /// gating blocks are 400ms long and overlap by 75%, so they're made up of four steps
+    step: usize,
+    /// the sum of the squared, weighted samples of the current step
+    sum: f64,
+    /// the number of samples in the current step
+    len: usize,
+    /// the sums of all the finished steps
+    steps: Vec<f64>,
+    /// the largest absolute sample so far
+    peak: f32,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            filters: [KWeighting::new(sample_rate), KWeighting::new(sample_rate)],
+            step: (sample_rate / 10) as usize * 2,
+            sum: 0.0,
+            len: 0,
+            steps: Vec::new(),
+            peak: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, sample: f32) {
+        self.peak = self.peak.max(sample.abs());
+
+        // steps are made up of whole frames, so this is the channel of the sample
+        self.sum += self.filters[self.len % 2]
+            .process(f64::from(sample))
+            .powi(2);
+        self.len += 1;
+
+        if self.len == self.step {
+            self.steps.push(self.sum);
+            self.sum = 0.0;
+            self.len = 0;
+        }
+    }
+
+    /// the sample peak, in dBFS
+    pub fn peak(&self) -> f32 {
+        20.0 * self.peak.log10()
+    }
+
+    /// the integrated loudness, in LUFS
+    pub fn loudness(&self) -> f32 {
+        let blocks = self
+            .steps
+            .windows(4)
+            .map(|w| w.iter().sum::<f64>() / (self.step * 2) as f64);
+
+        // every block has to pass the absolute gate, and the relative gate is computed from those
+        let blocks = blocks
+            .filter(|&block| block_loudness(block) > ABSOLUTE_GATE)
+            .collect::<Vec<_>>();
+
+        let relative_gate = block_loudness(mean(&blocks)) - 10.0;
+
+        let blocks = blocks
+            .into_iter()
+            .filter(|&block| block_loudness(block) > relative_gate)
+            .collect::<Vec<_>>();
+
+        block_loudness(mean(&blocks)) as f32
+    }
+}
+
+fn mean(blocks: &[f64]) -> f64 {
+    if blocks.is_empty() {
+        0.0
+    } else {
+        blocks.iter().sum::<f64>() / blocks.len() as f64
+    }
+}
+
+fn block_loudness(mean_square: f64) -> f64 {
+    10.0f64.mul_add(mean_square.log10(), -0.691)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoudnessMeter;
+    use std::f64::consts::TAU;
+
+    /// a 997 Hz sine on both channels, at 48 kHz
+    fn sine(amplitude: f64, seconds: usize) -> impl Iterator<Item = f32> {
+        (0..48000 * seconds).flat_map(move |i| {
+            let s = (amplitude * (TAU * 997.0 * i as f64 / 48000.0).sin()) as f32;
+            [s, s]
+        })
+    }
+
+    fn measure(samples: impl Iterator<Item = f32>) -> LoudnessMeter {
+        let mut meter = LoudnessMeter::new(48000);
+        samples.for_each(|s| meter.push(s));
+        meter
+    }
+
+    #[test]
+    fn full_scale_sine() {
+        let meter = measure(sine(1.0, 5));
+
+        // -3.01 LUFS per channel
+        assert!(meter.loudness().abs() < 0.1, "{}", meter.loudness());
+        assert!(meter.peak().abs() < 0.05, "{}", meter.peak());
+    }
+
+    #[test]
+    fn quiet_sine() {
+        let meter = measure(sine(0.1, 5));
+
+        assert!(
+            (meter.loudness() + 20.0).abs() < 0.1,
+            "{}",
+            meter.loudness()
+        );
+        assert!((meter.peak() + 20.0).abs() < 0.05, "{}", meter.peak());
+    }
+
+    #[test]
+    fn absolute_gate_ignores_silence() {
+        let loud = measure(sine(1.0, 5)).loudness();
+        let with_silence = measure(sine(1.0, 5).chain(sine(0.0, 5))).loudness();
+
+        // only the blocks that straddle the end of the sine are slightly quieter
+        assert!((loud - with_silence).abs() < 0.25, "{loud} {with_silence}");
+    }
+
+    #[test]
+    fn relative_gate_ignores_quiet_parts() {
+        let loud = measure(sine(1.0, 5)).loudness();
+        let with_quiet = measure(sine(1.0, 5).chain(sine(0.01, 5))).loudness();
+
+        // without the relative gate, this would be about 3 LU quieter
+        assert!((loud - with_quiet).abs() < 0.25, "{loud} {with_quiet}");
+    }
+}
@@ -1,3 +1,12 @@
+//! the audio engine of generic daw
+//!
+//! an [`Arrangement`] holds the tracks, clips and [`Meter`] of a song, and can be built with an
+//! [`ArrangementBuilder`]. it can either be played back live by handing it to
+//! [`build_output_stream`] and using its transport methods, or rendered offline with
+//! [`Arrangement::export`], without ever touching an audio device.
+//!
+//! see `examples/render.rs` for a minimal offline render
+
 use audio_graph::AudioGraphNodeImpl as _;
 use cpal::{
     traits::{DeviceTrait as _, HostTrait as _, StreamTrait as _},
@@ -5,29 +14,41 @@ use cpal::{
 };
 use include_data::include_f32s;
 use std::sync::{atomic::Ordering::SeqCst, Arc};
+use varispeed::Varispeed;
 
 mod arrangement;
+mod arrangement_builder;
 mod denominator;
+mod export;
 mod live_sample;
 mod meter;
 mod numerator;
+mod overlap_policy;
 mod position;
+mod signal_generator;
+mod tap;
 mod track;
 mod track_clip;
+mod varispeed;
 
 pub use arrangement::Arrangement;
+pub use arrangement_builder::ArrangementBuilder;
 pub use clap_host;
 pub use cpal::Stream;
 pub use denominator::Denominator;
+pub use export::{BitDepth, Dithering, ExportOptions, LoudnessReport, Normalization};
 pub use live_sample::LiveSample;
 pub use meter::Meter;
 pub use numerator::Numerator;
+pub use overlap_policy::OverlapPolicy;
 pub use position::Position;
+pub use signal_generator::{Signal, SignalGenerator};
+pub use tap::Tap;
 pub(crate) use track::midi_track::dirty_event::DirtyEvent;
 pub use track::{audio_track::AudioTrack, midi_track::MidiTrack, Track};
 pub use track_clip::{
     audio_clip::{
-        interleaved_audio::{resample, InterleavedAudio},
+        interleaved_audio::{resample, InterleavedAudio, MusicalKey, ResampleQuality},
         AudioClip,
     },
     midi_clip::{midi_note::MidiNote, midi_pattern::MidiPattern, MidiClip},
@@ -37,6 +58,9 @@ pub use track_clip::{
 static ON_BAR_CLICK: &[f32] = include_f32s!("../../assets/on_bar_click.pcm");
 static OFF_BAR_CLICK: &[f32] = include_f32s!("../../assets/off_bar_click.pcm");
 
+/// starts playing `arrangement` back on the default output device
+///
+/// the stream stops when it's dropped
 pub fn build_output_stream(arrangement: Arc<Arrangement>) -> Stream {
     let device = cpal::default_host().default_output_device().unwrap();
     let config: &StreamConfig = &device.default_output_config().unwrap().into();
@@ -47,27 +71,53 @@ pub fn build_output_stream(arrangement: Arc<Arrangement>) -> Stream {
         .store(config.sample_rate.0, SeqCst);
 
     arrangement.on_bar_click.get_or_init(|| {
-        resample(44100, config.sample_rate.0, ON_BAR_CLICK.into())
-            .unwrap()
-            .into()
+        resample(
+            44100,
+            config.sample_rate.0,
+            ON_BAR_CLICK.into(),
+            ResampleQuality::High,
+        )
+        .unwrap()
+        .into()
     });
     arrangement.off_bar_click.get_or_init(|| {
-        resample(44100, config.sample_rate.0, OFF_BAR_CLICK.into())
-            .unwrap()
-            .into()
+        resample(
+            44100,
+            config.sample_rate.0,
+            OFF_BAR_CLICK.into(),
+            ResampleQuality::High,
+        )
+        .unwrap()
+        .into()
     });
 
+    let mut varispeed = Varispeed::default();
+
     let stream = device
         .build_output_stream(
             config,
-            move |data, _| {
-                let sample = if arrangement.meter.playing.load(SeqCst) {
-                    arrangement.meter.sample.fetch_add(data.len(), SeqCst)
+            move |data: &mut [f32], _| {
+                // the graph belongs to the export while it's running
+                if arrangement.meter.exporting.load(SeqCst) {
+                    data.fill(0.0);
+                    return;
+                }
+
+                let speed = arrangement.meter.speed.load(SeqCst).clamp(25, 200);
+
+                if arrangement.meter.playing.load(SeqCst) && speed != 100 {
+                    varispeed.fill_buf(&arrangement, f32::from(speed) / 100.0, data);
                 } else {
-                    arrangement.meter.sample.load(SeqCst)
-                };
+                    varispeed.reset();
 
-                arrangement.fill_buf(sample, data);
+                    let sample = if arrangement.meter.playing.load(SeqCst) {
+                        arrangement.meter.sample.fetch_add(data.len(), SeqCst)
+                    } else {
+                        arrangement.meter.sample.load(SeqCst)
+                    };
+
+                    arrangement.fill_buf(sample, data);
+                }
 
                 for s in data {
                     *s = s.clamp(-1.0, 1.0);
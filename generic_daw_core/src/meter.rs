@@ -22,6 +22,10 @@ pub struct Meter {
     pub exporting: AtomicBool,
     /// the current global time of the playhead, in samples
     pub sample: AtomicUsize,
+    /// the playback speed of the output stream in percent, clamped to between 25 and 200
+    ///
+    /// this doesn't affect the tempo of the arrangement, or exports
+    pub speed: AtomicU16,
 }
 
 impl Default for Meter {
@@ -34,6 +38,7 @@ impl Default for Meter {
             playing: AtomicBool::default(),
             exporting: AtomicBool::default(),
             sample: AtomicUsize::default(),
+            speed: AtomicU16::new(100),
         }
     }
 }
@@ -43,5 +48,6 @@ impl Meter {
         self.bpm.store(140, SeqCst);
         self.numerator.store(Numerator::default(), SeqCst);
         self.denominator.store(Denominator::default(), SeqCst);
+        self.speed.store(100, SeqCst);
     }
 }
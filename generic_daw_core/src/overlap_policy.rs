@@ -0,0 +1,21 @@
+use std::fmt::Display;
+use strum::VariantArray;
+
+/// what happens to the clips underneath a clip that's dropped on top of them
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum OverlapPolicy {
+    /// overlapping clips are summed
+    #[default]
+    Allow,
+    /// the parts of the underlying clips that are covered by the dropped clip are removed
+    TrimUnderlying,
+}
+
+impl Display for OverlapPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Allow => "Allow Overlap",
+            Self::TrimUnderlying => "Trim Underlying",
+        })
+    }
+}
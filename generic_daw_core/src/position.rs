@@ -6,7 +6,7 @@ use std::{
     sync::atomic::Ordering::SeqCst,
 };
 
-#[derive(Atom, AtomInteger, Clone, Copy, Default, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Atom, AtomInteger, Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Position(u32);
 
 impl Debug for Position {
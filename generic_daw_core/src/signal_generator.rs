@@ -0,0 +1,131 @@
+use crate::Meter;
+use std::{
+    f32::consts::TAU,
+    fmt::Display,
+    sync::{atomic::Ordering::SeqCst, Arc, Mutex},
+};
+use strum::VariantArray;
+
+/// the level of the generated signals, about -12 dBFS
+const AMPLITUDE: f32 = 0.25;
+/// the length of a single sine sweep, in seconds
+const SWEEP_LENGTH: f32 = 10.0;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum Signal {
+    /// a logarithmic sweep from 20 Hz to 20 kHz
+    #[default]
+    SineSweep,
+    WhiteNoise,
+    PinkNoise,
+    /// a full-scale single sample impulse, once every second
+    Impulse,
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::SineSweep => "Sine Sweep",
+            Self::WhiteNoise => "White Noise",
+            Self::PinkNoise => "Pink Noise",
+            Self::Impulse => "Impulse",
+        })
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    /// the number of frames generated so far
+    frame: usize,
+    /// the phase of the sine sweep
+    phase: f32,
+    /// state of the pseudo-random number generator used for noise
+    rng: u32,
+    /// the state of the filters used to turn white noise into pink noise
+    pink: [f32; 3],
+}
+
+/// a test tone that's mixed into the master output, after the audio graph
+///
+/// it isn't part of the audio graph, so it can't be routed through a track
+#[derive(Debug)]
+pub struct SignalGenerator {
+    signal: Signal,
+    state: Mutex<State>,
+    meter: Arc<Meter>,
+}
+
+impl SignalGenerator {
+    #[must_use]
+    pub fn create(signal: Signal, meter: Arc<Meter>) -> Self {
+        Self {
+            signal,
+            state: Mutex::new(State {
+                frame: 0,
+                phase: 0.0,
+                rng: 0x9e37_79b9,
+                pink: [0.0; 3],
+            }),
+            meter,
+        }
+    }
+
+    /// adds the next `buf.len() / 2` frames of the signal to `buf`
+    pub fn mix_into(&self, buf: &mut [f32]) {
+        let sample_rate = self.meter.sample_rate.load(SeqCst) as f32;
+        let mut state = self.state.lock().unwrap();
+
+        buf.chunks_exact_mut(2).for_each(|frame| {
+            let s = state.next(self.signal, sample_rate);
+
+            frame[0] += s;
+            frame[1] += s;
+        });
+    }
+}
+
+impl State {
+    fn next(&mut self, signal: Signal, sample_rate: f32) -> f32 {
+        let frame = self.frame;
+        self.frame += 1;
+
+        match signal {
+            Signal::SineSweep => {
+                let t = (frame as f32 / sample_rate) % SWEEP_LENGTH;
+                let frequency = 20.0 * 1000f32.powf(t / SWEEP_LENGTH);
+
+                self.phase = (frequency * TAU).mul_add(sample_rate.recip(), self.phase) % TAU;
+
+                self.phase.sin() * AMPLITUDE
+            }
+            Signal::WhiteNoise => self.random() * AMPLITUDE,
+            Signal::PinkNoise => {
+                // Paul Kellet's economy pink noise filter
+                let white = self.random();
+
+                self.pink[0] = 0.997_65f32.mul_add(self.pink[0], white * 0.099_046);
+                self.pink[1] = 0.963f32.mul_add(self.pink[1], white * 0.296_516_4);
+                self.pink[2] = 0.57f32.mul_add(self.pink[2], white * 1.052_691_3);
+
+                white.mul_add(0.1848, self.pink.iter().sum::<f32>()) * AMPLITUDE * 0.5
+            }
+            Signal::Impulse => {
+                if frame % (sample_rate as usize).max(1) == 0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// a uniformly distributed number in `-1.0..1.0`
+    fn random(&mut self) -> f32 {
+        // xorshift32
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+
+        (f64::from(self.rng) / f64::from(u32::MAX)).mul_add(2.0, -1.0) as f32
+    }
+}
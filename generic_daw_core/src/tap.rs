@@ -0,0 +1,123 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+/// the number of stereo frames that are kept
+const LEN: usize = 2048;
+
+/// a copy of the most recent frames of the live output, for the stereo meters
+#[derive(Debug)]
+pub struct Tap {
+    frames: Mutex<VecDeque<[f32; 2]>>,
+}
+
+impl Default for Tap {
+    fn default() -> Self {
+        Self {
+            frames: Mutex::new(VecDeque::with_capacity(LEN)),
+        }
+    }
+}
+
+impl Tap {
+    /// appends the interleaved frames in `buf`, dropping the oldest ones
+    ///
+    /// this is called from the audio thread, so it skips the buffer instead of waiting for the
+    /// lock
+    pub(crate) fn push(&self, buf: &[f32]) {
+        let Ok(mut frames) = self.frames.try_lock() else {
+            return;
+        };
+
+        for frame in buf.chunks_exact(2) {
+            if frames.len() == LEN {
+                frames.pop_front();
+            }
+
+            frames.push_back([frame[0], frame[1]]);
+        }
+    }
+
+    /// the most recent frames, oldest first
+    #[must_use]
+    pub fn frames(&self) -> Vec<[f32; 2]> {
+        self.frames.lock().unwrap().iter().copied().collect()
+    }
+
+    /// whether all of the most recent frames are silent
+    #[must_use]
+    pub fn is_silent(&self) -> bool {
+        self.frames
+            .lock()
+            .unwrap()
+            .iter()
+            .flatten()
+            .all(|s| *s == 0.0)
+    }
+
+    /// the correlation between the two channels, from -1 when they're opposite to 1 when they're
+    /// the same, or `None` when either of them is silent
+    #[must_use]
+    pub fn correlation(&self) -> Option<f32> {
+        let (lr, ll, rr) = self
+            .frames
+            .lock()
+            .unwrap()
+            .iter()
+            .fold((0.0, 0.0, 0.0), |(lr, ll, rr), &[l, r]| {
+                (l.mul_add(r, lr), l.mul_add(l, ll), r.mul_add(r, rr))
+            });
+
+        let energy = (ll * rr).sqrt();
+
+        (energy > f32::EPSILON).then(|| lr / energy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tap;
+
+    fn tap(frames: impl IntoIterator<Item = [f32; 2]>) -> Tap {
+        let tap = Tap::default();
+        tap.push(&frames.into_iter().flatten().collect::<Vec<_>>());
+        tap
+    }
+
+    fn sine() -> impl Iterator<Item = f32> {
+        (0..4096).map(|i| (i as f32 * 0.05).sin())
+    }
+
+    #[test]
+    fn mono() {
+        let correlation = tap(sine().map(|s| [s, s])).correlation().unwrap();
+
+        assert!((correlation - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn inverted() {
+        let correlation = tap(sine().map(|s| [s, -s])).correlation().unwrap();
+
+        assert!((correlation + 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn one_sided() {
+        assert_eq!(tap(sine().map(|s| [s, 0.0])).correlation(), None);
+    }
+
+    #[test]
+    fn silent() {
+        assert!(tap([[0.0, 0.0]; 16]).is_silent());
+        assert!(!tap([[0.0, 0.0], [0.0, 0.1]]).is_silent());
+    }
+
+    #[test]
+    fn keeps_the_latest_frames() {
+        let tap = tap((0..3000).map(|i| [i as f32, 0.0]));
+        let frames = tap.frames();
+
+        assert_eq!(frames.len(), 2048);
+        assert_eq!(frames[0], [952.0, 0.0]);
+        assert_eq!(frames[2047], [2999.0, 0.0]);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{Meter, Position, TrackClip};
-use audio_graph::{pan, AudioGraphNodeImpl};
+use crate::{Meter, OverlapPolicy, Position, TrackClip};
+use audio_graph::{AudioGraphNodeImpl, SmoothedGain};
 use audio_track::AudioTrack;
 use midi_track::MidiTrack;
 use std::sync::{atomic::Ordering::SeqCst, Arc, Mutex, RwLock};
@@ -30,14 +30,19 @@ impl AudioGraphNodeImpl for Track {
             Self::Midi(_) => unimplemented!(),
         }
 
-        let volume = self.get_volume();
-        let (lpan, rpan) = pan(self.get_pan());
+        if self.meter().exporting.load(SeqCst) {
+            SmoothedGain::snap(self.get_volume(), self.get_pan(), &mut track_buf);
+        } else {
+            self.gain().apply(
+                buf_start_sample,
+                self.get_volume(),
+                self.get_pan(),
+                &mut track_buf,
+            );
+        }
 
         track_buf
             .iter()
-            .map(|s| s * volume)
-            .enumerate()
-            .map(|(i, s)| if i % 2 == 0 { s * lpan } else { s * rpan })
             .zip(buf)
             .for_each(|(sample, buf)| *buf += sample);
     }
@@ -99,6 +104,72 @@ impl Track {
         }
     }
 
+    /// splits `clip` in two at `at`, if `at` lies inside of it
+    pub fn split_clip(&self, clip: &Arc<TrackClip>, at: Position) {
+        if at <= clip.get_global_start() || at >= clip.get_global_end() {
+            return;
+        }
+
+        let tail = Arc::new((**clip).clone());
+        tail.trim_start_to(at);
+        clip.trim_end_to(at);
+
+        let pushed = self.try_push(&tail);
+        debug_assert!(pushed);
+    }
+
+    /// trims or removes the other clips on this track that overlap with `clip`, according to
+    /// `policy`, returning the clips that were removed entirely
+    #[must_use]
+    pub fn resolve_overlaps(
+        &self,
+        clip: &Arc<TrackClip>,
+        policy: OverlapPolicy,
+    ) -> Vec<Arc<TrackClip>> {
+        let mut removed = Vec::new();
+
+        if policy == OverlapPolicy::Allow {
+            return removed;
+        }
+
+        let start = clip.get_global_start();
+        let end = clip.get_global_end();
+
+        let overlapping = self
+            .clips()
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|other| !Arc::ptr_eq(other, clip))
+            .filter(|other| other.get_global_start() < end && other.get_global_end() > start)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for other in overlapping {
+            let other_start = other.get_global_start();
+            let other_end = other.get_global_end();
+
+            if other_start < start {
+                if other_end > end {
+                    // keep the part of the underlying clip that sticks out after the dropped clip
+                    let tail = Arc::new((*other).clone());
+                    tail.trim_start_to(end);
+                    let pushed = self.try_push(&tail);
+                    debug_assert!(pushed);
+                }
+
+                other.trim_end_to(start);
+            } else if other_end > end {
+                other.trim_start_to(end);
+            } else {
+                self.remove_clip(&other);
+                removed.push(other);
+            }
+        }
+
+        removed
+    }
+
     #[must_use]
     pub fn len(&self) -> Position {
         match self {
@@ -107,6 +178,13 @@ impl Track {
         }
     }
 
+    fn gain(&self) -> &SmoothedGain {
+        match self {
+            Self::Audio(track) => &track.gain,
+            Self::Midi(track) => &track.gain,
+        }
+    }
+
     #[must_use]
     pub fn get_volume(&self) -> f32 {
         match self {
@@ -137,3 +215,129 @@ impl Track {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioTrack, Track};
+    use crate::{AudioClip, InterleavedAudio, Meter, OverlapPolicy, Position, TrackClip};
+    use std::sync::{atomic::Ordering::SeqCst, Arc};
+
+    /// an empty audio track, along with its meter
+    fn track() -> (Arc<Track>, Arc<Meter>) {
+        let meter = Arc::new(Meter::default());
+        meter.sample_rate.store(44100, SeqCst);
+
+        let track = AudioTrack::create(meter.clone())
+            .downcast_arc::<Track>()
+            .unwrap();
+
+        (track, meter)
+    }
+
+    /// adds a clip from quarter note `start` to quarter note `end` to `track`
+    fn add(track: &Track, meter: &Arc<Meter>, start: u32, end: u32) -> Arc<TrackClip> {
+        // four seconds, which is a bit more than nine quarter notes at the default tempo
+        let clip = AudioClip::create(InterleavedAudio::silence(44100 * 4 * 2), meter.clone());
+        clip.move_to(Position::new(start, 0));
+        clip.trim_end_to(Position::new(end, 0));
+
+        assert!(track.try_push(&clip));
+
+        clip
+    }
+
+    /// the start and end of every clip on `track`, in quarter notes, sorted by start
+    fn spans(track: &Track) -> Vec<(u32, u32)> {
+        let mut spans = track
+            .clips()
+            .read()
+            .unwrap()
+            .iter()
+            .map(|clip| {
+                (
+                    clip.get_global_start().quarter_note(),
+                    clip.get_global_end().quarter_note(),
+                )
+            })
+            .collect::<Vec<_>>();
+        spans.sort_unstable();
+        spans
+    }
+
+    /// the offset of the clip on `track` that starts at quarter note `start`, in quarter notes
+    fn offset(track: &Track, start: u32) -> u32 {
+        track
+            .clips()
+            .read()
+            .unwrap()
+            .iter()
+            .find(|clip| clip.get_global_start() == Position::new(start, 0))
+            .unwrap()
+            .get_clip_start()
+            .quarter_note()
+    }
+
+    #[test]
+    fn split_clip() {
+        let (track, meter) = track();
+        let clip = add(&track, &meter, 0, 8);
+
+        track.split_clip(&clip, Position::new(3, 0));
+
+        assert_eq!(spans(&track), [(0, 3), (3, 8)]);
+        assert_eq!(offset(&track, 3), 3);
+    }
+
+    #[test]
+    fn split_clip_outside() {
+        let (track, meter) = track();
+        let clip = add(&track, &meter, 2, 8);
+
+        track.split_clip(&clip, Position::new(2, 0));
+        track.split_clip(&clip, Position::new(8, 0));
+        track.split_clip(&clip, Position::new(9, 0));
+
+        assert_eq!(spans(&track), [(2, 8)]);
+    }
+
+    #[test]
+    fn resolve_overlaps_inside() {
+        let (track, meter) = track();
+        add(&track, &meter, 0, 8);
+        let dropped = add(&track, &meter, 2, 4);
+
+        let removed = track.resolve_overlaps(&dropped, OverlapPolicy::TrimUnderlying);
+
+        assert!(removed.is_empty());
+        assert_eq!(spans(&track), [(0, 2), (2, 4), (4, 8)]);
+        assert_eq!(offset(&track, 4), 4);
+    }
+
+    #[test]
+    fn resolve_overlaps_partial() {
+        let (track, meter) = track();
+        add(&track, &meter, 0, 3);
+        let covered = add(&track, &meter, 3, 5);
+        add(&track, &meter, 5, 9);
+        let dropped = add(&track, &meter, 2, 6);
+
+        let removed = track.resolve_overlaps(&dropped, OverlapPolicy::TrimUnderlying);
+
+        assert_eq!(removed.len(), 1);
+        assert!(Arc::ptr_eq(&removed[0], &covered));
+        assert_eq!(spans(&track), [(0, 2), (2, 6), (6, 9)]);
+        assert_eq!(offset(&track, 6), 1);
+    }
+
+    #[test]
+    fn resolve_overlaps_allow() {
+        let (track, meter) = track();
+        add(&track, &meter, 0, 8);
+        let dropped = add(&track, &meter, 2, 4);
+
+        let removed = track.resolve_overlaps(&dropped, OverlapPolicy::Allow);
+
+        assert!(removed.is_empty());
+        assert_eq!(spans(&track), [(0, 8), (2, 4)]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{Meter, Position, Track, TrackClip};
 use atomig::Atomic;
-use audio_graph::AudioGraphNodeImpl;
+use audio_graph::{AudioGraphNodeImpl, SmoothedGain};
 use std::sync::{atomic::Ordering::SeqCst, Arc, RwLock};
 
 #[derive(Debug)]
@@ -11,6 +11,8 @@ pub struct AudioTrack {
     pub volume: Atomic<f32>,
     /// -1 <= pan <= 1
     pub pan: Atomic<f32>,
+    /// smooths out changes to `volume` and `pan`
+    pub(crate) gain: SmoothedGain,
     pub(crate) meter: Arc<Meter>,
 }
 
@@ -35,6 +37,7 @@ impl AudioTrack {
             clips: RwLock::default(),
             volume: Atomic::new(1.0),
             pan: Atomic::new(0.0),
+            gain: SmoothedGain::default(),
             meter,
         }))
     }
@@ -1,5 +1,6 @@
 use crate::{Meter, Position, Track, TrackClip};
 use atomig::Atomic;
+use audio_graph::SmoothedGain;
 use clap_host::PluginAudioProcessor;
 use plugin_state::PluginState;
 use std::sync::{Arc, Mutex, RwLock};
@@ -15,6 +16,8 @@ pub struct MidiTrack {
     pub volume: Atomic<f32>,
     /// -1 <= pan <= 1
     pub pan: Atomic<f32>,
+    /// smooths out changes to `volume` and `pan`
+    pub(crate) gain: SmoothedGain,
     /// holds all the state needed for a generator plugin to function properly
     pub(crate) plugin_state: Mutex<PluginState>,
     pub(crate) meter: Arc<Meter>,
@@ -27,6 +30,7 @@ impl MidiTrack {
             clips: RwLock::default(),
             volume: Atomic::new(1.0),
             pan: Atomic::new(0.0),
+            gain: SmoothedGain::default(),
             plugin_state: PluginState::create(plugin),
             meter,
         }))
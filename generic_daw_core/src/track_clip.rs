@@ -65,6 +65,30 @@ impl TrackClip {
         }
     }
 
+    /// the start of the clip relative to the start of its sample or pattern
+    #[must_use]
+    pub fn get_clip_start(&self) -> Position {
+        match self {
+            Self::Audio(audio) => audio.get_clip_start(),
+            Self::Midi(midi) => midi.get_pattern_start(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_muted(&self) -> bool {
+        match self {
+            Self::Audio(audio) => audio.is_muted(),
+            Self::Midi(midi) => midi.is_muted(),
+        }
+    }
+
+    pub fn toggle_mute(&self) {
+        match self {
+            Self::Audio(audio) => audio.toggle_mute(),
+            Self::Midi(midi) => midi.toggle_mute(),
+        }
+    }
+
     pub fn trim_start_to(&self, clip_start: Position) {
         match self {
             Self::Audio(audio) => audio.trim_start_to(clip_start),
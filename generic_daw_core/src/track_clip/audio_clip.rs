@@ -2,7 +2,10 @@ use crate::{Meter, Position, TrackClip};
 use atomig::Atomic;
 use audio_graph::AudioGraphNodeImpl;
 use interleaved_audio::InterleavedAudio;
-use std::sync::{atomic::Ordering::SeqCst, Arc};
+use std::sync::{
+    atomic::{AtomicBool, Ordering::SeqCst},
+    Arc,
+};
 
 pub mod interleaved_audio;
 
@@ -15,6 +18,8 @@ pub struct AudioClip {
     global_end: Atomic<Position>,
     /// the start of the clip relative to the start of the sample
     clip_start: Atomic<Position>,
+    /// whether the clip is skipped during playback
+    muted: AtomicBool,
     pub meter: Arc<Meter>,
 }
 
@@ -25,6 +30,7 @@ impl Clone for AudioClip {
             global_start: Atomic::new(self.global_start.load(SeqCst)),
             global_end: Atomic::new(self.global_end.load(SeqCst)),
             clip_start: Atomic::new(self.clip_start.load(SeqCst)),
+            muted: AtomicBool::new(self.muted.load(SeqCst)),
             meter: self.meter.clone(),
         }
     }
@@ -32,6 +38,20 @@ impl Clone for AudioClip {
 
 impl AudioGraphNodeImpl for AudioClip {
     fn fill_buf(&self, buf_start_sample: usize, buf: &mut [f32]) {
+        if self.is_muted() {
+            return;
+        }
+
+        self.audio
+            .playback_samples(self.meter.exporting.load(SeqCst), |samples| {
+                self.mix(samples, buf_start_sample, buf);
+            });
+    }
+}
+
+impl AudioClip {
+    /// adds the part of `samples` that's playing in `buf` to it
+    fn mix(&self, samples: &[f32], buf_start_sample: usize, buf: &mut [f32]) {
         let clip_start_sample = self
             .global_start
             .load(SeqCst)
@@ -46,11 +66,11 @@ impl AudioGraphNodeImpl for AudioClip {
                     .load(SeqCst)
                     .in_interleaved_samples(&self.meter);
 
-            if start_index >= self.audio.samples.len() {
+            if start_index >= samples.len() {
                 return;
             }
 
-            self.audio.samples[start_index..]
+            samples[start_index..]
                 .iter()
                 .zip(buf)
                 .for_each(|(sample, buf)| {
@@ -61,8 +81,7 @@ impl AudioGraphNodeImpl for AudioClip {
                 return;
             }
 
-            self.audio
-                .samples
+            samples
                 .iter()
                 .zip(buf[diff..].iter_mut())
                 .for_each(|(sample, buf)| {
@@ -82,6 +101,7 @@ impl AudioClip {
             global_start: Atomic::default(),
             global_end: Atomic::new(Position::from_interleaved_samples(samples, &meter)),
             clip_start: Atomic::default(),
+            muted: AtomicBool::default(),
             meter,
         }))
     }
@@ -101,6 +121,15 @@ impl AudioClip {
         self.clip_start.load(SeqCst)
     }
 
+    #[must_use]
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(SeqCst)
+    }
+
+    pub fn toggle_mute(&self) {
+        self.muted.fetch_not(SeqCst);
+    }
+
     pub fn trim_start_to(&self, global_start: Position) {
         let global_start = global_start.clamp(
             self.get_global_start()
@@ -2,16 +2,21 @@ use crate::Meter;
 use anyhow::Result;
 use itertools::{Itertools as _, MinMaxResult};
 use rubato::{
-    Resampler as _, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
+    FastFixedIn, PolynomialDegree, Resampler as _, SincFixedIn, SincInterpolationParameters,
+    SincInterpolationType, WindowFunction,
 };
 use std::{
     array,
     cmp::{max_by, min_by},
-    fmt::Debug,
+    collections::HashMap,
+    fmt::{Debug, Display},
     fs::File,
-    path::PathBuf,
-    sync::{atomic::Ordering::SeqCst, Arc, RwLock},
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    io::{BufRead as _, BufReader},
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering::SeqCst, Arc, LazyLock, Mutex, RwLock, Weak},
 };
+use strum::VariantArray;
 use symphonia::core::{
     audio::SampleBuffer,
     codecs::DecoderOptions,
@@ -21,14 +26,61 @@ use symphonia::core::{
     probe::Hint,
 };
 
+mod key;
+mod tempo;
+
+pub use key::MusicalKey;
+
+/// all the samples that are currently loaded, keyed by a hash of their file contents and how they
+/// were resampled
+static LOADED: LazyLock<Mutex<HashMap<u64, Weak<InterleavedAudio>>>> =
+    LazyLock::new(Mutex::default);
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, VariantArray)]
+pub enum ResampleQuality {
+    /// linear interpolation, for when speed matters more than quality
+    Fast,
+    /// windowed sinc interpolation
+    #[default]
+    High,
+}
+
+impl Display for ResampleQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Fast => "Fast Resampling",
+            Self::High => "High Quality Resampling",
+        })
+    }
+}
+
+/// what's needed to resample a sample that was loaded with fast resampling at high quality
+#[derive(Debug)]
+struct Source {
+    /// the sample rate of the file
+    sample_rate: u32,
+    /// the decoded samples of the file, before resampling
+    samples: Box<[f32]>,
+    /// `samples`, resampled at high quality, only kept around during an export
+    resampled: RwLock<Option<Box<[f32]>>>,
+}
+
 #[expect(clippy::type_complexity)]
 pub struct InterleavedAudio {
-    /// these are used to play the sample back
-    pub(crate) samples: Box<[f32]>,
-    /// these are used to draw the sample in various quality levels
-    pub lods: [RwLock<Box<[(f32, f32)]>>; 10],
+    /// these are used to play the sample back, and are shared between identical files
+    pub(crate) samples: Arc<[f32]>,
+    /// these are used to draw the sample in various quality levels, and are shared between
+    /// identical files
+    pub lods: Arc<[RwLock<Box<[(f32, f32)]>>; 10]>,
     /// the file name associated with the sample
     pub(crate) path: PathBuf,
+    /// the detected tempo of the sample, in BPM
+    bpm: Option<f32>,
+    /// the detected key of the sample
+    key: Option<MusicalKey>,
+    /// the source audio, kept around only when the sample was resampled with a fast method, so
+    /// that exports can still use high quality resampling
+    source: Option<Arc<Source>>,
 }
 
 impl Debug for InterleavedAudio {
@@ -40,22 +92,83 @@ impl Debug for InterleavedAudio {
 }
 
 impl InterleavedAudio {
-    pub fn create(path: PathBuf, meter: &Meter) -> Result<Arc<Self>> {
-        let samples = Self::read_audio_file(&path, meter)?;
+    /// loads the sample at `path`, sharing its buffers with any identical sample that's already
+    /// loaded
+    pub fn create(path: PathBuf, meter: &Meter, quality: ResampleQuality) -> Result<Arc<Self>> {
+        let hash = Self::hash(&path, meter, quality)?;
+
+        let loaded = LOADED.lock().unwrap().get(&hash).and_then(Weak::upgrade);
+        if let Some(audio) = loaded {
+            if audio.path == path {
+                return Ok(audio);
+            }
+
+            // the hashes only say that the files are probably the same
+            if files_equal(&audio.path, &path).unwrap_or(false) {
+                return Ok(Arc::new(Self {
+                    samples: audio.samples.clone(),
+                    lods: audio.lods.clone(),
+                    path,
+                    bpm: audio.bpm,
+                    key: audio.key,
+                    source: audio.source.clone(),
+                }));
+            }
+        }
+
+        let (file_sample_rate, decoded) = Self::read_audio_file(&path)?;
+        let stream_sample_rate = meter.sample_rate.load(SeqCst);
+
+        let source = (quality != ResampleQuality::High && file_sample_rate != stream_sample_rate)
+            .then(|| {
+                Arc::new(Source {
+                    sample_rate: file_sample_rate,
+                    samples: decoded.clone().into_boxed_slice(),
+                    resampled: RwLock::default(),
+                })
+            });
+
+        let samples: Arc<[f32]> =
+            resample(file_sample_rate, stream_sample_rate, decoded, quality)?.into();
         let length = samples.len();
+        let bpm = tempo::detect(&samples, meter.sample_rate.load(SeqCst));
+        let key = key::detect(&samples, meter.sample_rate.load(SeqCst));
 
         let audio = Arc::new(Self {
             samples,
-            lods: array::from_fn(|i| {
+            lods: Arc::new(array::from_fn(|i| {
                 RwLock::new(vec![(0.0, 0.0); length.div_ceil(1 << (i + 3))].into_boxed_slice())
-            }),
+            })),
             path,
+            bpm,
+            key,
+            source,
         });
 
         Self::create_lod(&audio);
+
+        let mut loaded = LOADED.lock().unwrap();
+        loaded.retain(|_, audio| audio.strong_count() != 0);
+        loaded.insert(hash, Arc::downgrade(&audio));
+
         Ok(audio)
     }
 
+    /// `len` interleaved samples of silence, not backed by any file
+    #[cfg(test)]
+    pub(crate) fn silence(len: usize) -> Arc<Self> {
+        Arc::new(Self {
+            samples: vec![0.0; len].into(),
+            lods: Arc::new(array::from_fn(|i| {
+                RwLock::new(vec![(0.5, 0.5); len.div_ceil(1 << (i + 3))].into_boxed_slice())
+            })),
+            path: PathBuf::new(),
+            bpm: None,
+            key: None,
+            source: None,
+        })
+    }
+
     #[must_use]
     pub fn len(&self) -> usize {
         self.samples.len()
@@ -66,12 +179,95 @@ impl InterleavedAudio {
         self.len() == 0
     }
 
-    fn read_audio_file(path: &PathBuf, meter: &Meter) -> Result<Box<[f32]>> {
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// the detected tempo of the sample, in BPM, if it has a clear pulse
+    #[must_use]
+    pub fn bpm(&self) -> Option<f32> {
+        self.bpm
+    }
+
+    /// the detected key of the sample, if it has any pitched content
+    #[must_use]
+    pub fn key(&self) -> Option<MusicalKey> {
+        self.key
+    }
+
+    /// hashes the contents of the file at `path` in chunks, together with how it's resampled
+    fn hash(path: &Path, meter: &Meter, quality: ResampleQuality) -> Result<u64> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut hasher = DefaultHasher::new();
+
+        loop {
+            let chunk = file.fill_buf()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            hasher.write(chunk);
+
+            let len = chunk.len();
+            file.consume(len);
+        }
+
+        meter.sample_rate.load(SeqCst).hash(&mut hasher);
+        quality.hash(&mut hasher);
+
+        Ok(hasher.finish())
+    }
+
+    /// resamples the sample at high quality ahead of an export, if it was loaded with a faster
+    /// method
+    pub(crate) fn prepare_export(&self, meter: &Meter) -> Result<()> {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+
+        if source.resampled.read().unwrap().is_none() {
+            let resampled = resample(
+                source.sample_rate,
+                meter.sample_rate.load(SeqCst),
+                source.samples.to_vec(),
+                ResampleQuality::High,
+            )?;
+
+            *source.resampled.write().unwrap() = Some(resampled.into_boxed_slice());
+        }
+
+        Ok(())
+    }
+
+    /// drops the high quality samples made by [`Self::prepare_export`]
+    pub(crate) fn finish_export(&self) {
+        if let Some(source) = &self.source {
+            *source.resampled.write().unwrap() = None;
+        }
+    }
+
+    /// calls `f` with the samples to play back, which are the high quality ones while exporting,
+    /// once they're prepared
+    pub(crate) fn playback_samples<R>(&self, exporting: bool, f: impl FnOnce(&[f32]) -> R) -> R {
+        if exporting {
+            if let Some(source) = &self.source {
+                if let Some(resampled) = &*source.resampled.read().unwrap() {
+                    return f(resampled);
+                }
+            }
+        }
+
+        f(&self.samples)
+    }
+
+    /// decodes the file at `path`, returning its sample rate and interleaved samples
+    fn read_audio_file(path: &Path) -> Result<(u32, Vec<f32>)> {
         let mut format = symphonia::default::get_probe()
             .format(
                 &Hint::default(),
                 MediaSourceStream::new(
-                    Box::new(File::open(path).unwrap()),
+                    Box::new(File::open(path)?),
                     MediaSourceStreamOptions::default(),
                 ),
                 &FormatOptions::default(),
@@ -110,10 +306,7 @@ impl InterleavedAudio {
             interleaved_samples.extend(buf.samples());
         }
 
-        let stream_sample_rate = meter.sample_rate.load(SeqCst);
-
-        resample(file_sample_rate, stream_sample_rate, interleaved_samples)
-            .map(Vec::into_boxed_slice)
+        Ok((file_sample_rate, interleaved_samples))
     }
 
     fn create_lod(audio: &Self) {
@@ -159,28 +352,14 @@ pub fn resample(
     file_sample_rate: u32,
     stream_sample_rate: u32,
     mut interleaved_samples: Vec<f32>,
+    quality: ResampleQuality,
 ) -> Result<Vec<f32>> {
     if file_sample_rate == stream_sample_rate {
         return Ok(interleaved_samples);
     }
 
     let resample_ratio = f64::from(stream_sample_rate) / f64::from(file_sample_rate);
-    let oversampling_factor =
-        (file_sample_rate / gcd(stream_sample_rate, file_sample_rate)) as usize;
-
-    let mut resampler = SincFixedIn::new(
-        resample_ratio,
-        1.0,
-        SincInterpolationParameters {
-            sinc_len: 256,
-            f_cutoff: 0.95,
-            interpolation: SincInterpolationType::Nearest,
-            oversampling_factor,
-            window: WindowFunction::Blackman,
-        },
-        interleaved_samples.len() / 2,
-        2,
-    )?;
+    let frames = interleaved_samples.len() / 2;
 
     let left = interleaved_samples
         .iter()
@@ -194,7 +373,33 @@ pub fn resample(
         .copied()
         .collect();
 
-    let deinterleaved_samples = resampler.process(&[left, right], None)?;
+    let input = [left, right];
+
+    let deinterleaved_samples = match quality {
+        ResampleQuality::Fast => {
+            FastFixedIn::new(resample_ratio, 1.0, PolynomialDegree::Linear, frames, 2)?
+                .process(&input, None)?
+        }
+        ResampleQuality::High => {
+            let oversampling_factor =
+                (file_sample_rate / gcd(stream_sample_rate, file_sample_rate)) as usize;
+
+            SincFixedIn::new(
+                resample_ratio,
+                1.0,
+                SincInterpolationParameters {
+                    sinc_len: 256,
+                    f_cutoff: 0.95,
+                    interpolation: SincInterpolationType::Nearest,
+                    oversampling_factor,
+                    window: WindowFunction::Blackman,
+                },
+                frames,
+                2,
+            )?
+            .process(&input, None)?
+        }
+    };
 
     interleaved_samples.clear();
     interleaved_samples.extend(
@@ -206,6 +411,34 @@ pub fn resample(
     Ok(interleaved_samples)
 }
 
+/// whether the files at `a` and `b` have the same contents
+fn files_equal(a: &Path, b: &Path) -> Result<bool> {
+    let (a, b) = (File::open(a)?, File::open(b)?);
+
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let (mut a, mut b) = (BufReader::new(a), BufReader::new(b));
+
+    loop {
+        let chunk_a = a.fill_buf()?;
+        let chunk_b = b.fill_buf()?;
+
+        if chunk_a.is_empty() || chunk_b.is_empty() {
+            return Ok(chunk_a.is_empty() && chunk_b.is_empty());
+        }
+
+        let len = chunk_a.len().min(chunk_b.len());
+        if chunk_a[..len] != chunk_b[..len] {
+            return Ok(false);
+        }
+
+        a.consume(len);
+        b.consume(len);
+    }
+}
+
 fn gcd(mut a: u32, mut b: u32) -> u32 {
     while b != 0 {
         a %= b;
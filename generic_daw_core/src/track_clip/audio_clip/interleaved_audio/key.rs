@@ -0,0 +1,174 @@
+use std::{f32::consts::TAU, fmt::Display};
+
+/// the audio is averaged down by this factor before analysis, since only low pitches are needed
+const DECIMATION: usize = 4;
+/// the length of the blocks the pitch content is measured over, in decimated samples
+const BLOCK_SIZE: usize = 4096;
+/// the frequency of C2, the lowest analyzed pitch
+const LOWEST_PITCH: f32 = 65.406;
+/// how many octaves are analyzed, starting at `LOWEST_PITCH`
+const OCTAVES: usize = 4;
+
+/// the Krumhansl-Kessler major key profile, starting at the tonic
+const MAJOR_PROFILE: [f32; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+/// the Krumhansl-Kessler minor key profile, starting at the tonic
+const MINOR_PROFILE: [f32; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+const PITCH_CLASSES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MusicalKey {
+    /// the pitch class of the tonic, where 0 is C
+    tonic: usize,
+    minor: bool,
+}
+
+impl Display for MusicalKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            PITCH_CLASSES[self.tonic],
+            if self.minor { "minor" } else { "major" }
+        )
+    }
+}
+
+/// estimates the key of interleaved stereo audio, by matching its chromagram against the key
+/// profiles of all 24 major and minor keys
+///
+/// returns `None` when the audio is too short or silent, or when every pitch class is equally
+/// strong
+pub fn detect(samples: &[f32], sample_rate: u32) -> Option<MusicalKey> {
+    let rate = sample_rate as f32 / DECIMATION as f32;
+
+    let mono = samples
+        .chunks_exact(2 * DECIMATION)
+        .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
+        .collect::<Vec<_>>();
+
+    let mut chroma = [0.0; 12];
+    mono.chunks_exact(BLOCK_SIZE).for_each(|block| {
+        (0..OCTAVES * 12).for_each(|pitch| {
+            let frequency = LOWEST_PITCH * (pitch as f32 / 12.0).exp2();
+            chroma[pitch % 12] += goertzel(block, frequency, rate).sqrt();
+        });
+    });
+
+    if chroma.iter().sum::<f32>() <= f32::EPSILON {
+        return None;
+    }
+
+    (0..12)
+        .flat_map(|tonic| {
+            [
+                MusicalKey {
+                    tonic,
+                    minor: false,
+                },
+                MusicalKey { tonic, minor: true },
+            ]
+        })
+        .filter_map(|key| {
+            let profile = if key.minor {
+                MINOR_PROFILE
+            } else {
+                MAJOR_PROFILE
+            };
+
+            Some((
+                key,
+                correlation(&chroma, |i| profile[(i + 12 - key.tonic) % 12])?,
+            ))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(key, _)| key)
+}
+
+/// the power of `block` at `frequency`
+fn goertzel(block: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+    let coefficient = 2.0 * (TAU * frequency / sample_rate).cos();
+
+    let (s1, s2) = block.iter().fold((0.0, 0.0), |(s1, s2), x| {
+        (coefficient.mul_add(s1, x - s2), s1)
+    });
+
+    s1.mul_add(s1, s2.mul_add(s2, -coefficient * s1 * s2))
+}
+
+/// the Pearson correlation between `chroma` and a key profile
+///
+/// returns `None` when either of them is flat, since the correlation isn't defined then
+fn correlation(chroma: &[f32; 12], profile: impl Fn(usize) -> f32) -> Option<f32> {
+    let chroma_mean = chroma.iter().sum::<f32>() / 12.0;
+    let profile_mean = (0..12).map(&profile).sum::<f32>() / 12.0;
+
+    let (covariance, chroma_variance, profile_variance) =
+        (0..12).fold((0.0, 0.0, 0.0), |(cov, cv, pv), i| {
+            let c = chroma[i] - chroma_mean;
+            let p = profile(i) - profile_mean;
+            (c.mul_add(p, cov), c.mul_add(c, cv), p.mul_add(p, pv))
+        });
+
+    let variance = chroma_variance * profile_variance;
+
+    (variance > 0.0).then(|| covariance / variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{correlation, detect, MusicalKey, MAJOR_PROFILE};
+    use std::f64::consts::TAU;
+
+    /// ten seconds of sines at `frequencies` on both channels, at 44.1 kHz
+    fn chord(frequencies: &[f64]) -> Vec<f32> {
+        (0..44100 * 10)
+            .flat_map(|i| {
+                let s = frequencies
+                    .iter()
+                    .map(|f| (TAU * f * f64::from(i) / 44100.0).sin())
+                    .sum::<f64>()
+                    / frequencies.len() as f64;
+                [s as f32; 2]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn major_triad() {
+        assert_eq!(
+            detect(&chord(&[261.63, 329.63, 392.0]), 44100),
+            Some(MusicalKey {
+                tonic: 0,
+                minor: false
+            })
+        );
+    }
+
+    #[test]
+    fn minor_triad() {
+        assert_eq!(
+            detect(&chord(&[220.0, 261.63, 329.63]), 44100),
+            Some(MusicalKey {
+                tonic: 9,
+                minor: true
+            })
+        );
+    }
+
+    #[test]
+    fn silence() {
+        assert_eq!(detect(&vec![0.0; 44100 * 10 * 2], 44100), None);
+    }
+
+    #[test]
+    fn flat_chroma() {
+        assert_eq!(correlation(&[1.0; 12], |i| MAJOR_PROFILE[i]), None);
+    }
+}
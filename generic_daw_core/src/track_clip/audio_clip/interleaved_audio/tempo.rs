@@ -0,0 +1,89 @@
+/// how many onset envelope values are computed per second
+const ENVELOPE_RATE: u32 = 200;
+/// the slowest tempo that's detected, slower tempos are detected at a multiple of it
+const MIN_BPM: f32 = 70.0;
+/// the fastest tempo that's detected, faster tempos are detected at a fraction of it
+const MAX_BPM: f32 = 180.0;
+
+/// estimates the tempo of interleaved stereo audio, in BPM, from the periodicity of its onsets
+///
+/// returns `None` when the audio is too short or has no clear pulse
+pub fn detect(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    let hop = (sample_rate / ENVELOPE_RATE) as usize * 2;
+    if hop == 0 {
+        return None;
+    }
+
+    let energies = samples
+        .chunks_exact(hop)
+        .map(|chunk| chunk.iter().map(|s| s * s).sum::<f32>().ln_1p())
+        .collect::<Vec<_>>();
+
+    // the half-wave rectified change in log energy peaks at note onsets
+    let onsets = energies
+        .windows(2)
+        .map(|w| (w[1] - w[0]).max(0.0))
+        .collect::<Vec<_>>();
+
+    let min_lag = (60.0 * ENVELOPE_RATE as f32 / MAX_BPM) as usize;
+    let max_lag = (60.0 * ENVELOPE_RATE as f32 / MIN_BPM) as usize;
+
+    // at least four beats at the slowest tempo are needed to find a pulse
+    if onsets.len() < max_lag * 4 {
+        return None;
+    }
+
+    let autocorrelation = |lag: usize| {
+        onsets
+            .iter()
+            .zip(&onsets[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f32>()
+            / (onsets.len() - lag) as f32
+    };
+
+    let (lag, strength) = (min_lag..=max_lag)
+        .map(|lag| (lag, autocorrelation(lag)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    if strength <= autocorrelation(0) * 0.1 {
+        return None;
+    }
+
+    // refine the lag by fitting a parabola through its neighbours
+    let before = autocorrelation(lag - 1);
+    let after = autocorrelation(lag + 1);
+    let curvature = 2.0f32.mul_add(-strength, before + after);
+    let offset = if curvature < 0.0 {
+        0.5 * (before - after) / curvature
+    } else {
+        0.0
+    };
+
+    Some(60.0 * ENVELOPE_RATE as f32 / (lag as f32 + offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect;
+
+    #[test]
+    fn click_track() {
+        // a click on every beat at 120 BPM, for ten seconds, each as long as one envelope value
+        let samples = (0..48000 * 10)
+            .flat_map(|frame| {
+                let s = if frame % 24000 < 240 { 1.0 } else { 0.0 };
+                [s, s]
+            })
+            .collect::<Vec<f32>>();
+
+        let bpm = detect(&samples, 48000).unwrap();
+        assert!((bpm - 120.0).abs() < 0.5, "{bpm}");
+    }
+
+    #[test]
+    fn no_pulse() {
+        assert_eq!(detect(&vec![0.0; 48000 * 20], 48000), None);
+        assert_eq!(detect(&[0.0; 100], 48000), None);
+    }
+}
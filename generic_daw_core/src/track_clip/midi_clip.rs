@@ -1,7 +1,10 @@
 use crate::{DirtyEvent, Meter, Position, TrackClip};
 use atomig::Atomic;
 use midi_pattern::MidiPattern;
-use std::sync::{atomic::Ordering::SeqCst, Arc};
+use std::sync::{
+    atomic::{AtomicBool, Ordering::SeqCst},
+    Arc,
+};
 
 pub mod midi_note;
 pub mod midi_pattern;
@@ -15,6 +18,8 @@ pub struct MidiClip {
     global_end: Atomic<Position>,
     /// the start of the clip relative to the start of the pattern
     pattern_start: Atomic<Position>,
+    /// whether the clip is skipped during playback
+    muted: AtomicBool,
     pub meter: Arc<Meter>,
 }
 
@@ -25,6 +30,7 @@ impl Clone for MidiClip {
             global_start: Atomic::new(self.global_start.load(SeqCst)),
             global_end: Atomic::new(self.global_end.load(SeqCst)),
             pattern_start: Atomic::new(self.pattern_start.load(SeqCst)),
+            muted: AtomicBool::new(self.muted.load(SeqCst)),
             meter: self.meter.clone(),
         }
     }
@@ -39,6 +45,7 @@ impl MidiClip {
             global_start: Atomic::default(),
             global_end: Atomic::new(Position::from_interleaved_samples(len, &meter)),
             pattern_start: Atomic::default(),
+            muted: AtomicBool::default(),
             meter,
         }))
     }
@@ -58,6 +65,15 @@ impl MidiClip {
         self.pattern_start.load(SeqCst)
     }
 
+    #[must_use]
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(SeqCst)
+    }
+
+    pub fn toggle_mute(&self) {
+        self.muted.fetch_not(SeqCst);
+    }
+
     pub fn trim_start_to(&self, global_start: Position) {
         let global_start = global_start.clamp(
             self.get_global_start()
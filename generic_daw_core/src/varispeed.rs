@@ -0,0 +1,120 @@
+use crate::Arrangement;
+use audio_graph::AudioGraphNodeImpl as _;
+use std::sync::atomic::Ordering::SeqCst;
+
+/// the highest playback speed, as a factor
+const MAX_SPEED: f32 = 2.0;
+/// the number of output frames rendered at once
+///
+/// output buffers are split into chunks of this size, so `buf` never has to grow on the audio
+/// thread, no matter what buffer size the device picks
+const CHUNK_FRAMES: usize = 512;
+
+/// plays back the output of an arrangement at a different speed, by linearly interpolating between
+/// its frames
+#[derive(Debug)]
+pub struct Varispeed {
+    /// the last two frames that were rendered, but aren't fully played back yet
+    frames: [f32; 4],
+    /// the position of the next output frame, relative to the first of `frames`
+    phase: f32,
+    /// large enough for a chunk of `CHUNK_FRAMES` at `MAX_SPEED`, plus `frames`
+    buf: Box<[f32]>,
+}
+
+impl Default for Varispeed {
+    fn default() -> Self {
+        Self {
+            frames: [0.0; 4],
+            phase: 0.0,
+            buf: vec![0.0; (CHUNK_FRAMES * MAX_SPEED as usize + 2) * 2].into_boxed_slice(),
+        }
+    }
+}
+
+impl Varispeed {
+    pub fn reset(&mut self) {
+        self.frames = [0.0; 4];
+        self.phase = 0.0;
+    }
+
+    pub fn fill_buf(&mut self, arrangement: &Arrangement, speed: f32, data: &mut [f32]) {
+        debug_assert!(speed <= MAX_SPEED);
+
+        for chunk in data.chunks_mut(CHUNK_FRAMES * 2) {
+            self.fill_chunk(arrangement, speed, chunk);
+        }
+    }
+
+    fn fill_chunk(&mut self, arrangement: &Arrangement, speed: f32, data: &mut [f32]) {
+        let end = ((data.len() / 2) as f32).mul_add(speed, self.phase);
+        let new = end as usize;
+
+        let buf = &mut self.buf[..(new + 2) * 2];
+        buf[..4].copy_from_slice(&self.frames);
+        buf[4..].fill(0.0);
+
+        let sample = arrangement.meter.sample.fetch_add(new * 2, SeqCst);
+        arrangement.fill_buf(sample, &mut buf[4..]);
+
+        data.chunks_exact_mut(2).enumerate().for_each(|(i, frame)| {
+            let pos = (i as f32).mul_add(speed, self.phase);
+            let idx = pos as usize * 2;
+            let t = pos.fract();
+
+            frame[0] = (buf[idx + 2] - buf[idx]).mul_add(t, buf[idx]);
+            frame[1] = (buf[idx + 3] - buf[idx + 1]).mul_add(t, buf[idx + 1]);
+        });
+
+        self.frames.copy_from_slice(&buf[new * 2..new * 2 + 4]);
+        self.phase = end.fract();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Varispeed;
+    use crate::{Arrangement, LiveSample};
+    use std::sync::{atomic::Ordering::SeqCst, Arc};
+
+    /// plays back a ramp, where every frame holds its own index, for 1000 frames at `speed`
+    ///
+    /// returns the output and how far the playhead moved
+    fn ramp(speed: f32) -> (Vec<f32>, usize) {
+        let arrangement = Arrangement::create();
+        let ramp = (0..4096u16)
+            .flat_map(|i| [f32::from(i); 2])
+            .collect::<Arc<[f32]>>();
+        arrangement
+            .live_sample_playback
+            .write()
+            .unwrap()
+            .push(LiveSample::new(ramp, 0));
+
+        let mut data = vec![0.0; 2000];
+        Varispeed::default().fill_buf(&arrangement, speed, &mut data);
+
+        (data, arrangement.meter.sample.load(SeqCst))
+    }
+
+    #[test]
+    fn interpolates_between_frames() {
+        for speed in [0.5, 2.0] {
+            let (data, _) = ramp(speed);
+
+            // the first two frames are the silent ones from before playback started
+            for (i, frame) in data.chunks_exact(2).enumerate() {
+                let expected = (i as f32).mul_add(speed, -2.0).max(0.0);
+
+                assert!((frame[0] - expected).abs() < 1e-3, "{i} {}", frame[0]);
+                assert!((frame[1] - expected).abs() < 1e-3, "{i} {}", frame[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn advances_by_speed() {
+        assert_eq!(ramp(0.5).1, 1000);
+        assert_eq!(ramp(2.0).1, 4000);
+    }
+}
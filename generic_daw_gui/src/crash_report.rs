@@ -0,0 +1,36 @@
+use home::home_dir;
+use std::{
+    backtrace::Backtrace,
+    env::consts::{ARCH, OS},
+    fs,
+    panic::{self, PanicHookInfo},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// installs a panic hook that writes a crash report to `~/.generic_daw/crash_reports`, before
+/// running the default panic hook
+pub fn install() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        write_report(info);
+        default_hook(info);
+    }));
+}
+
+fn write_report(info: &PanicHookInfo<'_>) -> Option<()> {
+    let dir = home_dir()?.join(".generic_daw").join("crash_reports");
+    fs::create_dir_all(&dir).ok()?;
+
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    let report = format!(
+        "{info}\n\nversion: {}\nos: {OS} {ARCH}\nthread: {}\n\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        thread::current().name().unwrap_or("<unnamed>"),
+        Backtrace::force_capture()
+    );
+
+    fs::write(dir.join(format!("crash-{time}.txt")), report).ok()
+}
@@ -1,36 +1,76 @@
 use crate::{
     clap_host::{ClapHost, Message as ClapHostMessage, OpenedMessage},
-    widget::{Arrangement, VSplit},
+    inspector::{Inspector, Message as InspectorMessage},
+    render_queue::{Message as RenderQueueMessage, RenderJob, RenderKind, RenderQueue},
+    widget::{Arrangement, StereoMeter, Tool, VSplit},
 };
 use generic_daw_core::{
     build_output_stream,
     clap_host::{clack_host::process::PluginAudioConfiguration, get_installed_plugins, open_gui},
-    Arrangement as ArrangementInner, AudioClip, AudioTrack, Denominator, InterleavedAudio,
-    Numerator, Stream, Track,
+    seconds_to_interleaved_samples, Arrangement as ArrangementInner, BitDepth, Denominator,
+    Dithering, ExportOptions, InterleavedAudio, Meter, Normalization, Numerator, OverlapPolicy,
+    Position, ResampleQuality, Signal, Stream, TrackClip,
 };
 use home::home_dir;
 use iced::{
     event::{self, Status},
     keyboard,
-    widget::{button, column, horizontal_space, pick_list, row, scrollable, toggler, Text},
+    widget::{
+        button, column, horizontal_space, pick_list, row, scrollable, text_input, toggler, Text,
+    },
     window::{self, Settings},
     Alignment::Center,
-    Element, Event, Subscription, Task, Theme,
+    Element, Event,
+    Length::Fill,
+    Subscription, Task, Theme,
 };
 use iced_aw::number_input;
 use iced_file_tree::file_tree;
 use iced_fonts::{bootstrap, BOOTSTRAP_FONT};
 use rfd::{AsyncFileDialog, FileHandle};
 use std::{
+    fmt::{Display, Formatter},
     path::PathBuf,
     sync::{atomic::Ordering::SeqCst, Arc, Mutex},
+    time::{Duration, Instant},
 };
-use strum::VariantArray as _;
+use strum::VariantArray;
 
 pub struct Daw {
     arrangement: Arc<ArrangementInner>,
     clap_host: ClapHost,
+    render_queue: RenderQueue,
+    inspector: Inspector,
     theme: Theme,
+    export_options: ExportOptions,
+    /// the file names of exported stems, see [`ArrangementInner::export_stems`]
+    stem_template: String,
+    stop_behavior: StopBehavior,
+    overlap_policy: OverlapPolicy,
+    /// what a plain left click does in the arrangement
+    tool: Tool,
+    /// the quality newly loaded samples are resampled with
+    resample_quality: ResampleQuality,
+    /// the folders shown in the file tree, in order
+    sample_roots: Vec<PathBuf>,
+    /// the test signal that's currently playing
+    signal: Option<Signal>,
+    /// the sample playback was last started from
+    play_start: usize,
+    /// whether the transport position is shown in seconds instead of bars and beats
+    show_seconds: bool,
+    /// the contents of the transport position field while it's being edited
+    seek_input: String,
+    /// whether the scroll direction in the arrangement is inverted
+    natural_scrolling: bool,
+    /// how fast the arrangement scrolls and zooms, in percent
+    scroll_sensitivity: u16,
+    /// the base text size, which the whole interface is scaled to
+    text_size: u16,
+    /// the most recent tempo taps, oldest first
+    taps: Vec<Instant>,
+    /// samples loaded while rendering, added once the render queue is done
+    pending_samples: Vec<Arc<InterleavedAudio>>,
     _stream: Stream,
 }
 
@@ -40,32 +80,100 @@ pub enum Message {
     Ping,
     ThemeChanged(Theme),
     ClapHost(ClapHostMessage),
+    RenderQueue(RenderQueueMessage),
+    Inspector(InspectorMessage),
     #[expect(dead_code)]
     Test,
     LoadSamplesButton,
     LoadSamples(Vec<FileHandle>),
     LoadSample(PathBuf),
     LoadedSample(Arc<InterleavedAudio>),
+    Audition(Arc<InterleavedAudio>),
+    AddSampleRootButton,
+    AddSampleRoot(FileHandle),
+    RemoveSampleRoot(usize),
+    MoveSampleRootUp(usize),
     ExportButton,
     Export(FileHandle),
+    ExportStemsButton,
+    ExportStems(FileHandle),
+    BitDepthChanged(BitDepth),
+    DitheringChanged(Dithering),
+    NormalizationChanged(Normalization),
+    NormalizationTargetChanged(f32),
+    StemTemplateChanged(String),
     TogglePlay,
     Stop,
+    PlayFromStart,
+    StopBehaviorChanged(StopBehavior),
+    OverlapPolicyChanged(OverlapPolicy),
+    ToolChanged(Tool),
+    CycleTool,
+    ResampleQualityChanged(ResampleQuality),
+    ToggleShowSeconds,
+    ToggleNaturalScrolling,
+    ScrollSensitivityChanged(u16),
+    TextSizeChanged(u16),
+    SeekInputChanged(String),
+    Seek,
+    GoTo,
     New,
     BpmChanged(u16),
+    TapTempo,
+    SpeedChanged(u16),
     NumeratorChanged(Numerator),
     DenominatorChanged(Denominator),
     ToggleMetronome,
+    SignalChanged(Option<Signal>),
+}
+
+/// what happens to the playhead when playback is stopped
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum StopBehavior {
+    StopInPlace,
+    ReturnToPlayStart,
+    #[default]
+    ReturnToZero,
+}
+
+impl Display for StopBehavior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::StopInPlace => "Stop in Place",
+            Self::ReturnToPlayStart => "Return to Play Start",
+            Self::ReturnToZero => "Return to Zero",
+        })
+    }
 }
 
 impl Default for Daw {
     fn default() -> Self {
         let arrangement = ArrangementInner::create();
         let stream = build_output_stream(arrangement.clone());
+        let render_queue = RenderQueue::new(arrangement.clone());
 
         Self {
             arrangement,
             clap_host: ClapHost::default(),
+            render_queue,
+            inspector: Inspector::default(),
             theme: Theme::Dark,
+            export_options: ExportOptions::default(),
+            stem_template: "{name}-{track}".to_owned(),
+            stop_behavior: StopBehavior::default(),
+            overlap_policy: OverlapPolicy::default(),
+            tool: Tool::default(),
+            resample_quality: ResampleQuality::default(),
+            sample_roots: vec![home_dir().unwrap()],
+            signal: None,
+            play_start: 0,
+            show_seconds: false,
+            seek_input: String::new(),
+            natural_scrolling: false,
+            scroll_sensitivity: 100,
+            text_size: DEFAULT_TEXT_SIZE,
+            taps: Vec::new(),
+            pending_samples: Vec::new(),
             _stream: stream,
         }
     }
@@ -74,12 +182,45 @@ impl Default for Daw {
 impl Daw {
     #[expect(clippy::too_many_lines)]
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        // the render thread reads the arrangement, so it mustn't change underneath it
+        if self.render_queue.is_rendering()
+            && matches!(
+                message,
+                Message::New
+                    | Message::TogglePlay
+                    | Message::PlayFromStart
+                    | Message::BpmChanged(..)
+                    | Message::TapTempo
+                    | Message::NumeratorChanged(..)
+                    | Message::DenominatorChanged(..)
+                    | Message::Inspector(
+                        InspectorMessage::StartChanged(..)
+                            | InspectorMessage::EndChanged(..)
+                            | InspectorMessage::OffsetChanged(..)
+                    )
+            )
+        {
+            return Task::none();
+        }
+
         match message {
             Message::Ping => {}
             Message::ThemeChanged(theme) => self.theme = theme,
             Message::ClapHost(message) => {
                 return self.clap_host.update(message).map(Message::ClapHost);
             }
+            Message::RenderQueue(message) => {
+                let task = self.render_queue.update(message).map(Message::RenderQueue);
+
+                if !self.render_queue.is_rendering() {
+                    for audio_file in self.pending_samples.drain(..) {
+                        self.arrangement.add_audio_track(audio_file);
+                    }
+                }
+
+                return task;
+            }
+            Message::Inspector(message) => self.inspector.update(message),
             Message::Test => {
                 let (id, fut) = window::open(Settings {
                     exit_on_close_request: false,
@@ -126,8 +267,10 @@ impl Daw {
                 let (tx, rx) = async_channel::bounded(1);
 
                 let arrangement = self.arrangement.clone();
+                let resample_quality = self.resample_quality;
                 std::thread::spawn(move || {
-                    let audio_file = InterleavedAudio::create(path, &arrangement.meter);
+                    let audio_file =
+                        InterleavedAudio::create(path, &arrangement.meter, resample_quality);
                     tx.send_blocking(audio_file).unwrap();
                 });
 
@@ -137,19 +280,28 @@ impl Daw {
                     .map(Message::LoadedSample);
             }
             Message::LoadedSample(audio_file) => {
-                let track = AudioTrack::create(self.arrangement.meter.clone());
-                debug_assert!(self.arrangement.audio_graph.add(track.clone().into()));
-                debug_assert!(self
-                    .arrangement
-                    .audio_graph
-                    .connect(&self.arrangement.audio_graph.root(), &track.clone().into()));
-                let track = track.downcast_arc::<Track>().unwrap();
-                debug_assert!(track.try_push(&AudioClip::create(
-                    audio_file,
-                    self.arrangement.meter.clone(),
-                )));
-                self.arrangement.tracks.write().unwrap().push(track);
+                if self.render_queue.is_rendering() {
+                    self.pending_samples.push(audio_file);
+                } else {
+                    self.arrangement.add_audio_track(audio_file);
+                }
+            }
+            Message::Audition(audio) => self.arrangement.audition(&audio),
+            Message::AddSampleRootButton => {
+                return Task::future(AsyncFileDialog::new().pick_folder())
+                    .and_then(Task::done)
+                    .map(Message::AddSampleRoot);
+            }
+            Message::AddSampleRoot(path) => {
+                let path = path.path().to_path_buf();
+                if !self.sample_roots.contains(&path) {
+                    self.sample_roots.push(path);
+                }
+            }
+            Message::RemoveSampleRoot(index) => {
+                self.sample_roots.remove(index);
             }
+            Message::MoveSampleRootUp(index) => self.sample_roots.swap(index - 1, index),
             Message::ExportButton => {
                 return Task::future(
                     AsyncFileDialog::new()
@@ -159,21 +311,90 @@ impl Daw {
                 .and_then(Task::done)
                 .map(Message::Export);
             }
-            Message::Export(path) => self.arrangement.export(path.path()),
+            Message::Export(path) => {
+                return self.update(Message::RenderQueue(RenderQueueMessage::Queue(RenderJob {
+                    kind: RenderKind::Mix,
+                    path: path.path().to_owned(),
+                    options: self.export_options,
+                })));
+            }
+            Message::ExportStemsButton => {
+                return Task::future(
+                    AsyncFileDialog::new()
+                        .add_filter("Wave File", &["wav"])
+                        .save_file(),
+                )
+                .and_then(Task::done)
+                .map(Message::ExportStems);
+            }
+            Message::ExportStems(path) => {
+                return self.update(Message::RenderQueue(RenderQueueMessage::Queue(RenderJob {
+                    kind: RenderKind::Stems(self.stem_template.clone()),
+                    path: path.path().to_owned(),
+                    options: self.export_options,
+                })));
+            }
+            Message::BitDepthChanged(bit_depth) => self.export_options.bit_depth = bit_depth,
+            Message::DitheringChanged(dithering) => self.export_options.dithering = dithering,
+            Message::NormalizationChanged(normalization) => {
+                self.export_options.normalization = normalization;
+                self.export_options.normalization_target = normalization.default_target();
+            }
+            Message::NormalizationTargetChanged(target) => {
+                self.export_options.normalization_target = target;
+            }
+            Message::StemTemplateChanged(stem_template) => self.stem_template = stem_template,
             Message::TogglePlay => {
-                self.arrangement.meter.playing.fetch_not(SeqCst);
+                if !self.arrangement.meter.playing.fetch_not(SeqCst) {
+                    self.play_start = self.arrangement.meter.sample.load(SeqCst);
+                }
             }
             Message::Stop => {
-                self.arrangement.meter.playing.store(false, SeqCst);
-                self.arrangement.meter.sample.store(0, SeqCst);
+                self.arrangement.pause();
+                match self.stop_behavior {
+                    StopBehavior::StopInPlace => {}
+                    StopBehavior::ReturnToPlayStart => {
+                        self.arrangement.meter.sample.store(self.play_start, SeqCst);
+                    }
+                    StopBehavior::ReturnToZero => self.arrangement.meter.sample.store(0, SeqCst),
+                }
                 self.arrangement
                     .live_sample_playback
                     .write()
                     .unwrap()
                     .clear();
             }
+            Message::PlayFromStart => {
+                self.arrangement.seek_to_sample(self.play_start);
+                self.arrangement.play();
+            }
+            Message::StopBehaviorChanged(stop_behavior) => self.stop_behavior = stop_behavior,
+            Message::OverlapPolicyChanged(overlap_policy) => self.overlap_policy = overlap_policy,
+            Message::ToolChanged(tool) => self.tool = tool,
+            Message::CycleTool => self.tool = self.tool.next(),
+            Message::ResampleQualityChanged(resample_quality) => {
+                self.resample_quality = resample_quality;
+            }
+            Message::ToggleShowSeconds => self.show_seconds ^= true,
+            Message::ToggleNaturalScrolling => self.natural_scrolling ^= true,
+            Message::ScrollSensitivityChanged(scroll_sensitivity) => {
+                self.scroll_sensitivity = scroll_sensitivity;
+            }
+            Message::TextSizeChanged(text_size) => self.text_size = text_size,
+            Message::SeekInputChanged(seek_input) => self.seek_input = seek_input,
+            Message::Seek => {
+                if let Some(sample) =
+                    parse_position(&self.seek_input, &self.arrangement.meter, self.show_seconds)
+                {
+                    self.arrangement.seek_to_sample(sample);
+                }
+                self.seek_input.clear();
+            }
+            Message::GoTo => return text_input::focus(SEEK_INPUT),
             Message::New => *self = Self::default(),
             Message::BpmChanged(bpm) => self.arrangement.meter.bpm.store(bpm, SeqCst),
+            Message::TapTempo => self.tap_tempo(),
+            Message::SpeedChanged(speed) => self.arrangement.meter.speed.store(speed, SeqCst),
             Message::NumeratorChanged(new_numerator) => self
                 .arrangement
                 .meter
@@ -187,18 +408,62 @@ impl Daw {
             Message::ToggleMetronome => {
                 self.arrangement.metronome.fetch_not(SeqCst);
             }
+            Message::SignalChanged(signal) => {
+                self.signal = signal;
+                self.arrangement.set_signal_generator(signal);
+            }
         }
 
         Task::none()
     }
 
     pub fn view(&self) -> Element<'_, Message> {
+        let rendering = self.render_queue.is_rendering();
+
         let controls = row![
             row![
                 button("Load Samples").on_press(Message::LoadSamplesButton),
                 button("Export").on_press(Message::ExportButton),
-                button("New").on_press(Message::New),
+                button("Export Stems").on_press(Message::ExportStemsButton),
+                button("New").on_press_maybe((!rendering).then_some(Message::New)),
             ],
+            pick_list(
+                ResampleQuality::VARIANTS,
+                Some(self.resample_quality),
+                Message::ResampleQualityChanged
+            ),
+            row![
+                pick_list(
+                    BitDepth::VARIANTS,
+                    Some(self.export_options.bit_depth),
+                    Message::BitDepthChanged
+                ),
+                pick_list(
+                    Dithering::VARIANTS,
+                    Some(self.export_options.dithering),
+                    Message::DitheringChanged
+                ),
+                pick_list(
+                    Normalization::VARIANTS,
+                    Some(self.export_options.normalization),
+                    Message::NormalizationChanged
+                ),
+            ]
+            .push_maybe(
+                (self.export_options.normalization != Normalization::None).then(|| {
+                    number_input(
+                        self.export_options.normalization_target,
+                        -60.0..=0.0,
+                        Message::NormalizationTargetChanged,
+                    )
+                    .width(50)
+                })
+            )
+            .push(
+                text_input("{name}-{track}", &self.stem_template)
+                    .on_input(Message::StemTemplateChanged)
+                    .width(120)
+            ),
             row![
                 button(
                     Text::new(bootstrap::icon_to_string(
@@ -210,12 +475,29 @@ impl Daw {
                     ))
                     .font(BOOTSTRAP_FONT)
                 )
-                .on_press(Message::TogglePlay),
+                .on_press_maybe((!rendering).then_some(Message::TogglePlay)),
                 button(
                     Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::StopFill))
                         .font(BOOTSTRAP_FONT)
                 )
                 .on_press(Message::Stop),
+                pick_list(
+                    StopBehavior::VARIANTS,
+                    Some(self.stop_behavior),
+                    Message::StopBehaviorChanged
+                ),
+            ],
+            row![
+                text_input(
+                    &format_position(&self.arrangement.meter, self.show_seconds),
+                    &self.seek_input
+                )
+                .id(SEEK_INPUT)
+                .on_input(Message::SeekInputChanged)
+                .on_submit(Message::Seek)
+                .width(80),
+                button(if self.show_seconds { "Time" } else { "Bars" })
+                    .on_press(Message::ToggleShowSeconds),
             ],
             row![
                 pick_list(
@@ -231,39 +513,226 @@ impl Daw {
                 )
                 .width(50),
             ],
-            number_input(
-                self.arrangement.meter.bpm.load(SeqCst),
-                30..=600,
-                Message::BpmChanged
-            )
-            .width(50),
+            row![
+                number_input(
+                    self.arrangement.meter.bpm.load(SeqCst),
+                    30..=600,
+                    Message::BpmChanged
+                )
+                .width(50),
+                button("Tap").on_press(Message::TapTempo),
+            ]
+            .spacing(5)
+            .align_y(Center),
+            row![
+                number_input(
+                    self.arrangement.meter.speed.load(SeqCst),
+                    25..=200,
+                    Message::SpeedChanged
+                )
+                .width(50),
+                Text::new("% speed"),
+            ]
+            .spacing(5)
+            .align_y(Center),
             toggler(self.arrangement.metronome.load(SeqCst))
                 .label("Metronome")
                 .on_toggle(|_| Message::ToggleMetronome),
+            row![pick_list(Signal::VARIANTS, self.signal, |signal| {
+                Message::SignalChanged(Some(signal))
+            })
+            .placeholder("Test Signal")]
+            .push_maybe(self.signal.map(|_| {
+                button(
+                    Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::StopFill))
+                        .font(BOOTSTRAP_FONT),
+                )
+                .on_press(Message::SignalChanged(None))
+            })),
+            pick_list(Tool::VARIANTS, Some(self.tool), Message::ToolChanged),
+            pick_list(
+                OverlapPolicy::VARIANTS,
+                Some(self.overlap_policy),
+                Message::OverlapPolicyChanged
+            ),
+            row![
+                number_input(
+                    self.scroll_sensitivity,
+                    10..=500,
+                    Message::ScrollSensitivityChanged
+                )
+                .width(50),
+                Text::new("% scroll"),
+            ]
+            .spacing(5)
+            .align_y(Center),
+            toggler(self.natural_scrolling)
+                .label("Natural Scrolling")
+                .on_toggle(|_| Message::ToggleNaturalScrolling),
+            row![
+                number_input(self.text_size, 10..=24, Message::TextSizeChanged).width(50),
+                Text::new("px text"),
+            ]
+            .spacing(5)
+            .align_y(Center),
             horizontal_space(),
             pick_list(Theme::ALL, Some(&self.theme), Message::ThemeChanged),
         ]
         .spacing(20)
         .align_y(Center);
 
-        let content = column![
-            controls,
-            VSplit::new(
-                scrollable(
-                    file_tree(home_dir().unwrap())
-                        .unwrap()
-                        .on_double_click(Message::LoadSample)
-                ),
-                Arrangement::new(self.arrangement.clone())
+        let content = column![controls, self.render_queue.view().map(Message::RenderQueue)]
+            .push_maybe(
+                self.inspector
+                    .view()
+                    .map(|inspector| inspector.map(Message::Inspector)),
             )
-            .split(0.25)
-        ]
-        .padding(20)
-        .spacing(20);
+            .push(
+                VSplit::new(
+                    column![
+                        scrollable(column![self.pool(), self.sample_roots()]).height(Fill),
+                        StereoMeter::new(self.arrangement.clone()),
+                    ]
+                    .spacing(20),
+                    Arrangement::new(self.arrangement.clone())
+                        .on_double_click(|clip| Message::Inspector(InspectorMessage::Inspect(clip)))
+                        .on_delete(|clip| Message::Inspector(InspectorMessage::Deleted(clip)))
+                        .revision(self.inspector.revision())
+                        .overlap_policy(self.overlap_policy)
+                        .tool(self.tool)
+                        .editable(!rendering)
+                        .natural_scrolling(self.natural_scrolling)
+                        .scroll_sensitivity(f32::from(self.scroll_sensitivity) / 100.0),
+                )
+                .split(0.25),
+            )
+            .padding(20)
+            .spacing(20);
 
         content.into()
     }
 
+    /// a file tree for every sample root, with controls to manage the roots
+    fn sample_roots(&self) -> Element<'_, Message> {
+        column(self.sample_roots.iter().enumerate().map(|(index, root)| {
+            column![row![
+                Text::new(
+                    root.file_name()
+                        .map_or_else(|| root.to_string_lossy(), |name| name.to_string_lossy())
+                ),
+                horizontal_space(),
+                button(
+                    Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::ArrowUp))
+                        .font(BOOTSTRAP_FONT)
+                )
+                .on_press_maybe((index != 0).then_some(Message::MoveSampleRootUp(index))),
+                button(
+                    Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::X))
+                        .font(BOOTSTRAP_FONT)
+                )
+                .on_press(Message::RemoveSampleRoot(index)),
+            ]
+            .spacing(5)
+            .align_y(Center),]
+            .push_maybe(
+                file_tree(root.clone())
+                    .map(|file_tree| file_tree.on_double_click(Message::LoadSample)),
+            )
+            .into()
+        }))
+        .push(button("Add Folder").on_press(Message::AddSampleRootButton))
+        .spacing(5)
+        .into()
+    }
+
+    /// sets the bpm from the average interval between the recent taps
+    fn tap_tempo(&mut self) {
+        const MAX_TAPS: usize = 8;
+        const MAX_INTERVAL: Duration = Duration::from_secs(2);
+
+        let now = Instant::now();
+
+        if self
+            .taps
+            .last()
+            .is_some_and(|last| now.duration_since(*last) > MAX_INTERVAL)
+        {
+            self.taps.clear();
+        }
+
+        self.taps.push(now);
+        if self.taps.len() > MAX_TAPS {
+            self.taps.remove(0);
+        }
+
+        if self.taps.len() < 2 {
+            return;
+        }
+
+        let interval =
+            now.duration_since(self.taps[0]).as_secs_f32() / (self.taps.len() - 1) as f32;
+        let bpm = (60.0 / interval).round().clamp(30.0, 600.0) as u16;
+
+        self.arrangement.meter.bpm.store(bpm, SeqCst);
+    }
+
+    /// lists all the samples used in the arrangement, with how many clips reference them
+    fn pool(&self) -> Element<'_, Message> {
+        let mut samples: Vec<(Arc<InterleavedAudio>, usize)> = Vec::new();
+
+        self.arrangement
+            .tracks
+            .read()
+            .unwrap()
+            .iter()
+            .for_each(|track| {
+                track.clips().read().unwrap().iter().for_each(|clip| {
+                    if let TrackClip::Audio(clip) = &**clip {
+                        if let Some((_, count)) = samples
+                            .iter_mut()
+                            .find(|(audio, _)| Arc::ptr_eq(audio, &clip.audio))
+                        {
+                            *count += 1;
+                        } else {
+                            samples.push((clip.audio.clone(), 1));
+                        }
+                    }
+                });
+            });
+
+        column(samples.into_iter().map(|(audio, count)| {
+            row![Text::new(format!(
+                "{} ({count})",
+                audio.path().file_name().unwrap().to_string_lossy()
+            ))]
+            .push_maybe(audio.key().map(|key| Text::new(key.to_string())))
+            .push_maybe(audio.bpm().map(|bpm| {
+                button(Text::new(format!("{bpm:.1} BPM")))
+                    .on_press(Message::BpmChanged((bpm.round() as u16).clamp(30, 600)))
+            }))
+            .extend([
+                horizontal_space().into(),
+                button(
+                    Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::PlayFill))
+                        .font(BOOTSTRAP_FONT),
+                )
+                .on_press(Message::Audition(audio.clone()))
+                .into(),
+                button(
+                    Text::new(bootstrap::icon_to_string(bootstrap::Bootstrap::Plus))
+                        .font(BOOTSTRAP_FONT),
+                )
+                .on_press(Message::LoadedSample(audio))
+                .into(),
+            ])
+            .spacing(5)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(5)
+        .into()
+    }
+
     pub fn subscription() -> Subscription<Message> {
         Subscription::batch([
             ClapHost::subscription().map(Message::ClapHost),
@@ -275,12 +744,25 @@ impl Daw {
                                 keyboard::Key::Named(keyboard::key::Named::Space) => {
                                     Some(Message::TogglePlay)
                                 }
+                                keyboard::Key::Named(keyboard::key::Named::Tab) => {
+                                    Some(Message::CycleTool)
+                                }
+                                keyboard::Key::Character(c) if c.as_str() == "t" => {
+                                    Some(Message::TapTempo)
+                                }
+                                _ => None,
+                            },
+                            (false, true, false) => match key {
+                                keyboard::Key::Named(keyboard::key::Named::Space) => {
+                                    Some(Message::PlayFromStart)
+                                }
                                 _ => None,
                             },
                             (true, false, false) => match key {
                                 keyboard::Key::Character(c) => match c.to_string().as_str() {
                                     "n" => Some(Message::New),
                                     "e" => Some(Message::ExportButton),
+                                    "g" => Some(Message::GoTo),
                                     _ => None,
                                 },
                                 _ => None,
@@ -298,4 +780,82 @@ impl Daw {
     pub fn theme(&self) -> Theme {
         self.theme.clone()
     }
+
+    /// scales the interface so that the default text size matches the chosen one
+    pub fn scale_factor(&self) -> f64 {
+        f64::from(self.text_size) / f64::from(DEFAULT_TEXT_SIZE)
+    }
+}
+
+/// the id of the transport position field
+const SEEK_INPUT: &str = "seek_input";
+
+/// the text size iced uses by default
+const DEFAULT_TEXT_SIZE: u16 = 16;
+
+/// formats the position of the playhead as `bar:beat`, or as `mm:ss.ss` when `in_seconds` is set
+fn format_position(meter: &Meter, in_seconds: bool) -> String {
+    let sample = meter.sample.load(SeqCst);
+
+    if in_seconds {
+        let seconds = sample as f32 / (meter.sample_rate.load(SeqCst) * 2) as f32;
+
+        format!("{:02}:{:05.2}", (seconds / 60.0) as u32, seconds % 60.0)
+    } else {
+        let numerator = meter.numerator.load(SeqCst) as u32;
+        let position = Position::from_interleaved_samples(sample, meter);
+
+        format!(
+            "{}:{}",
+            position.quarter_note() / numerator + 1,
+            position.quarter_note() % numerator + 1
+        )
+    }
+}
+
+/// parses a `bar:beat` or `bar` position, or a `mm:ss` or `ss` time when `in_seconds` is set,
+/// into a sample
+///
+/// returns `None` for anything that's malformed or past the largest representable position
+fn parse_position(input: &str, meter: &Meter, in_seconds: bool) -> Option<usize> {
+    const MAX_QUARTER_NOTE: u32 = u32::MAX >> 8;
+
+    let (major, minor) = input
+        .trim()
+        .split_once(':')
+        .map_or((None, input.trim()), |(major, minor)| (Some(major), minor));
+
+    if in_seconds {
+        let minutes = major.map_or(Some(0), |minutes| minutes.trim().parse::<u32>().ok())?;
+        let seconds = minor.trim().parse::<f32>().ok()?;
+
+        if !seconds.is_finite() || seconds < 0.0 {
+            return None;
+        }
+
+        let seconds = minutes.checked_mul(60)? as f32 + seconds;
+        let quarter_notes = seconds * f32::from(meter.bpm.load(SeqCst)) / 60.0;
+
+        (quarter_notes < MAX_QUARTER_NOTE as f32)
+            .then(|| seconds_to_interleaved_samples(seconds, meter) as usize)
+    } else {
+        let numerator = meter.numerator.load(SeqCst) as u32;
+
+        let (bar, beat) = match major {
+            Some(bar) => (
+                bar.trim().parse::<u32>().ok()?,
+                minor.trim().parse::<u32>().ok()?,
+            ),
+            None => (minor.parse::<u32>().ok()?, 1),
+        };
+
+        if bar == 0 || beat == 0 || beat > numerator {
+            return None;
+        }
+
+        let quarter_note = (bar - 1).checked_mul(numerator)?.checked_add(beat - 1)?;
+
+        (quarter_note <= MAX_QUARTER_NOTE)
+            .then(|| Position::new(quarter_note, 0).in_interleaved_samples(meter))
+    }
 }
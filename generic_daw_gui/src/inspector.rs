@@ -0,0 +1,132 @@
+use generic_daw_core::{Position, TrackClip};
+use iced::{
+    widget::{button, horizontal_space, row, Text},
+    Alignment::Center,
+    Element,
+};
+use iced_aw::number_input;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Inspect(Arc<TrackClip>),
+    Close,
+    /// a clip was deleted from the arrangement
+    Deleted(Arc<TrackClip>),
+    StartChanged(Position),
+    EndChanged(Position),
+    OffsetChanged(Position),
+}
+
+#[derive(Default)]
+pub struct Inspector {
+    /// the clip currently being inspected
+    clip: Option<Arc<TrackClip>>,
+    /// the number of edits made to clips so far
+    revision: u64,
+}
+
+impl Inspector {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Inspect(clip) => self.clip = Some(clip),
+            Message::Close => self.clip = None,
+            Message::Deleted(clip) => {
+                if self
+                    .clip
+                    .as_ref()
+                    .is_some_and(|inspected| Arc::ptr_eq(inspected, &clip))
+                {
+                    self.clip = None;
+                }
+            }
+            Message::StartChanged(start) => {
+                if let Some(clip) = &self.clip {
+                    clip.move_to(start);
+                    self.revision += 1;
+                }
+            }
+            Message::EndChanged(end) => {
+                if let Some(clip) = &self.clip {
+                    clip.trim_end_to(end);
+                    self.revision += 1;
+                }
+            }
+            Message::OffsetChanged(offset) => {
+                if let Some(clip) = &self.clip {
+                    // trimming the start of a clip moves its offset by the same amount
+                    let start = clip.get_global_start();
+                    let current = clip.get_clip_start();
+
+                    clip.trim_start_to(if offset > current {
+                        start + (offset - current)
+                    } else {
+                        start.saturating_sub(current - offset)
+                    });
+                    self.revision += 1;
+                }
+            }
+        }
+    }
+
+    /// changes whenever a clip is edited, so the arrangement knows to redraw it
+    pub const fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn view(&self) -> Option<Element<'_, Message>> {
+        let clip = self.clip.as_ref()?;
+
+        let source = match &**clip {
+            TrackClip::Audio(audio) => audio.audio.key().map_or_else(
+                || audio.audio.path().display().to_string(),
+                |key| format!("{} ({key})", audio.audio.path().display()),
+            ),
+            TrackClip::Midi(_) => clip.get_name(),
+        };
+
+        Some(
+            row![
+                Text::new(source),
+                position_input("Start", clip.get_global_start(), Message::StartChanged),
+                position_input("End", clip.get_global_end(), Message::EndChanged),
+                position_input("Offset", clip.get_clip_start(), Message::OffsetChanged),
+                horizontal_space(),
+                button("Close").on_press(Message::Close),
+            ]
+            .spacing(20)
+            .align_y(Center)
+            .into(),
+        )
+    }
+}
+
+/// a pair of inputs for the quarter note and sub-quarter note of a position
+fn position_input<'a>(
+    label: &'a str,
+    position: Position,
+    on_change: fn(Position) -> Message,
+) -> Element<'a, Message> {
+    row![
+        Text::new(label),
+        number_input(
+            position.quarter_note(),
+            0..=u32::MAX >> 8,
+            move |quarter_note| {
+                on_change(Position::new(quarter_note, position.sub_quarter_note()))
+            }
+        )
+        .width(70),
+        number_input(
+            position.sub_quarter_note(),
+            0..=255,
+            move |sub_quarter_note| {
+                on_change(Position::new(position.quarter_note(), sub_quarter_note))
+            }
+        )
+        .width(50),
+    ]
+    .spacing(5)
+    .align_y(Center)
+    .into()
+}
@@ -3,15 +3,23 @@ use iced::{application, Result};
 use iced_fonts::{BOOTSTRAP_FONT_BYTES, REQUIRED_FONT_BYTES};
 
 pub(crate) mod clap_host;
+pub(crate) mod crash_report;
 pub(crate) mod daw;
+pub(crate) mod inspector;
+pub(crate) mod render_queue;
 pub(crate) mod widget;
 
 fn main() -> Result {
+    crash_report::install();
+
     #[cfg(target_os = "linux")]
     {
-        // SAFETY:
-        // the program is single-threaded at this point
-        unsafe { std::env::remove_var("WAYLAND_DISPLAY") }
+        // plugin GUIs can't be embedded on wayland, so this forces everything onto xwayland
+        if std::env::var_os("GENERIC_DAW_NO_WAYLAND").is_some() {
+            // SAFETY:
+            // the program is single-threaded at this point
+            unsafe { std::env::remove_var("WAYLAND_DISPLAY") }
+        }
 
         if std::env::var("WINIT_X11_SCALE_FACTOR").is_err() {
             // SAFETY:
@@ -25,6 +33,7 @@ fn main() -> Result {
         .font(BOOTSTRAP_FONT_BYTES)
         .subscription(|_| Daw::subscription())
         .theme(Daw::theme)
+        .scale_factor(Daw::scale_factor)
         .antialiasing(true)
         .run()
 }
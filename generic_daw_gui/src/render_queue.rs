@@ -0,0 +1,198 @@
+use generic_daw_core::{Arrangement, ExportOptions, LoudnessReport};
+use iced::{
+    widget::{button, column, Text},
+    Element, Task,
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Queue(RenderJob),
+    /// a stem export started rendering the stem at the first index, out of the second
+    Stem(usize, usize, usize),
+    /// the result of a render, with the loudness report of a mix or the error that stopped it
+    Rendered(usize, Result<Option<LoudnessReport>, String>),
+    ClearFinished,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RenderKind {
+    Mix,
+    /// one file per track, named after the template
+    Stems(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderJob {
+    pub kind: RenderKind,
+    pub path: PathBuf,
+    pub options: ExportOptions,
+}
+
+impl Display for RenderJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}, {})",
+            self.path.file_name().unwrap_or_default().to_string_lossy(),
+            match self.kind {
+                RenderKind::Mix => "mix",
+                RenderKind::Stems(..) => "stems",
+            },
+            self.options.bit_depth
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Status {
+    Queued,
+    /// the index of the stem that's being rendered and the number of stems, for stem exports
+    Rendering(Option<(usize, usize)>),
+    /// stem exports don't produce a loudness report
+    Done(Option<LoudnessReport>),
+    Failed(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Queued => f.write_str("queued"),
+            Self::Rendering(None) => f.write_str("rendering..."),
+            Self::Rendering(Some((stem, stems))) => {
+                write!(f, "rendering stem {} of {stems}...", stem + 1)
+            }
+            Self::Done(None) => f.write_str("done"),
+            Self::Done(Some(report)) => write!(f, "done, {report}"),
+            Self::Failed(err) => write!(f, "failed: {err}"),
+        }
+    }
+}
+
+impl Status {
+    fn finished(&self) -> bool {
+        matches!(self, Self::Done(..) | Self::Failed(..))
+    }
+}
+
+pub struct RenderQueue {
+    arrangement: Arc<Arrangement>,
+    /// all the jobs in the queue with their ids, in the order they were added
+    jobs: Vec<(usize, RenderJob, Status)>,
+    /// the id of the next job that's added
+    next_id: usize,
+}
+
+impl RenderQueue {
+    pub fn new(arrangement: Arc<Arrangement>) -> Self {
+        Self {
+            arrangement,
+            jobs: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Queue(job) => {
+                self.jobs.push((self.next_id, job, Status::Queued));
+                self.next_id += 1;
+            }
+            Message::Stem(id, stem, stems) => {
+                if let Some((_, _, status @ Status::Rendering(..))) =
+                    self.jobs.iter_mut().find(|(job_id, ..)| *job_id == id)
+                {
+                    *status = Status::Rendering(Some((stem, stems)));
+                }
+            }
+            Message::Rendered(id, result) => {
+                if let Some((_, _, status @ Status::Rendering(..))) =
+                    self.jobs.iter_mut().find(|(job_id, ..)| *job_id == id)
+                {
+                    *status = match result {
+                        Ok(report) => Status::Done(report),
+                        Err(err) => Status::Failed(err),
+                    };
+                }
+            }
+            Message::ClearFinished => self.jobs.retain(|(_, _, status)| !status.finished()),
+        }
+
+        self.start_next()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        column(
+            self.jobs
+                .iter()
+                .map(|(_, job, status)| Text::new(format!("{job}: {status}")).into()),
+        )
+        .push_maybe(
+            self.jobs
+                .iter()
+                .any(|(_, _, status)| status.finished())
+                .then(|| button("Clear Finished").on_press(Message::ClearFinished)),
+        )
+        .spacing(5)
+        .into()
+    }
+
+    /// whether a job is currently being rendered
+    pub fn is_rendering(&self) -> bool {
+        self.jobs
+            .iter()
+            .any(|(_, _, status)| matches!(status, Status::Rendering(..)))
+    }
+
+    /// starts rendering the next queued job, if nothing else is being rendered
+    fn start_next(&mut self) -> Task<Message> {
+        if self.is_rendering() {
+            return Task::none();
+        }
+
+        let Some((id, job, status)) = self
+            .jobs
+            .iter_mut()
+            .find(|(_, _, status)| matches!(status, Status::Queued))
+        else {
+            return Task::none();
+        };
+
+        *status = Status::Rendering(None);
+
+        let (tx, rx) = async_channel::bounded(1);
+        let (stem_tx, stem_rx) = async_channel::unbounded();
+
+        let id = *id;
+        let arrangement = self.arrangement.clone();
+        let job = job.clone();
+        std::thread::spawn(move || {
+            let result = match &job.kind {
+                RenderKind::Mix => arrangement.export(&job.path, job.options).map(Some),
+                RenderKind::Stems(template) => arrangement
+                    .export_stems(&job.path, template, job.options, |stem, stems| {
+                        // the receiver only goes away when the app is closed
+                        stem_tx.send_blocking((stem, stems)).ok();
+                    })
+                    .map(|()| None),
+            };
+            tx.send_blocking(result.map_err(|err| err.to_string()))
+                .unwrap();
+        });
+
+        Task::batch([
+            Task::run(stem_rx, move |(stem, stems)| Message::Stem(id, stem, stems)),
+            // the sender is only dropped without sending anything if the render thread panicked
+            Task::future(async move { rx.recv().await }).map(move |result| {
+                Message::Rendered(
+                    id,
+                    result.unwrap_or_else(|_| Err("the render thread panicked".to_owned())),
+                )
+            }),
+        ])
+    }
+}
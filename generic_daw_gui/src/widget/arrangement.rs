@@ -1,5 +1,5 @@
-use super::{ArrangementPosition, ArrangementScale, Track, LINE_HEIGHT};
-use generic_daw_core::{Arrangement as ArrangementInner, Position, TrackClip};
+use super::{ArrangementPosition, ArrangementScale, Tool, Track, LINE_HEIGHT};
+use generic_daw_core::{Arrangement as ArrangementInner, OverlapPolicy, Position, TrackClip};
 use iced::{
     advanced::{
         graphics::geometry::Renderer as _,
@@ -13,8 +13,9 @@ use iced::{
     event::Status,
     keyboard::{self, Modifiers},
     mouse::{self, Cursor, Interaction, ScrollDelta},
+    time::{Duration, Instant},
     widget::text::{LineHeight, Shaping, Wrapping},
-    Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector,
+    window, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector,
 };
 use iced_wgpu::{
     geometry::Cache,
@@ -28,6 +29,9 @@ use std::{
     sync::{atomic::Ordering::SeqCst, Arc},
 };
 
+/// how long the window has to stay the same size before the waveforms are rebuilt
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(150);
+
 #[derive(Default)]
 enum Action {
     #[default]
@@ -47,14 +51,28 @@ struct State<'a, Message> {
     tracks: RefCell<Vec<Track<'a, Message>>>,
     /// saves the bpm from the last draw
     bpm: Cell<u16>,
+    /// saves the revision from the last draw
+    revision: Cell<u64>,
     /// caches the meshes of the waveforms
     waveform_cache: RefCell<Option<Cache>>,
     /// the current modifiers
     modifiers: Modifiers,
     /// the current action
     action: Action,
+    /// the track and position of the clip being dragged, when the drag started
+    drag_origin: Option<(usize, Position)>,
+    /// the last clip that was dragged, which the draw tool places copies of
+    last_clip: Option<Arc<TrackClip>>,
+    /// the last left click, used to detect double clicks
+    last_click: Option<mouse::Click>,
     /// the last window size
     last_bounds: Cell<Option<Rectangle>>,
+    /// the pixel column of the playhead when a redraw was last published
+    last_playhead_x: Cell<Option<i32>>,
+    /// when the window was last resized
+    resized_at: Cell<Option<Instant>>,
+    /// whether the waveform cache was drawn at a different size than the current one
+    stale_size: Cell<bool>,
     /// the theme of the last draw
     last_theme: RefCell<Option<Theme>>,
 }
@@ -63,6 +81,22 @@ pub struct Arrangement<'a, Message> {
     inner: Arc<ArrangementInner>,
     /// list of all the track widgets
     tracks: RefCell<Vec<Element<'a, Message, Theme, Renderer>>>,
+    /// the message to publish when a clip is double clicked
+    on_double_click: Option<Box<dyn Fn(Arc<TrackClip>) -> Message + 'a>>,
+    /// the message to publish when a clip is deleted
+    on_delete: Option<Box<dyn Fn(Arc<TrackClip>) -> Message + 'a>>,
+    /// what happens to the clips underneath a dropped clip
+    overlap_policy: OverlapPolicy,
+    /// what a plain left click does
+    tool: Tool,
+    /// whether the scroll direction is inverted
+    natural_scrolling: bool,
+    /// the factor all scroll deltas are multiplied by
+    scroll_sensitivity: f32,
+    /// whether clips and the playhead can be changed, scrolling and zooming always work
+    editable: bool,
+    /// bumped whenever clips are changed from outside of the widget, so the waveforms are rebuilt
+    revision: u64,
 }
 
 impl<Message> Debug for Arrangement<'_, Message> {
@@ -188,7 +222,15 @@ where
 
             shell.publish(Message::default());
         } else if self.inner.meter.playing.load(SeqCst) {
-            shell.publish(Message::default());
+            let x = ((self.inner.meter.sample.load(SeqCst) as f32 - state.position.x.get())
+                / state.scale.x.get().exp2()) as i32;
+
+            // nothing visible changes until the playhead moves to the next pixel
+            if state.last_playhead_x.replace(Some(x)) == Some(x) {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            } else {
+                shell.publish(Message::default());
+            }
         }
 
         if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
@@ -196,6 +238,13 @@ where
             return Status::Ignored;
         }
 
+        if let Event::Window(window::Event::Resized(..)) = event {
+            let now = Instant::now();
+            state.resized_at.set(Some(now));
+            shell.request_redraw(window::RedrawRequest::At(now + RESIZE_DEBOUNCE));
+            return Status::Ignored;
+        }
+
         let bounds = layout.bounds();
 
         let Some(pos) = cursor.position_in(bounds) else {
@@ -203,6 +252,20 @@ where
             return Status::Ignored;
         };
 
+        let event = match event {
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                Event::Mouse(mouse::Event::WheelScrolled {
+                    delta: self.adjust_scroll(delta),
+                })
+            }
+            event => event,
+        };
+
+        if !self.editable && !matches!(event, Event::Mouse(mouse::Event::WheelScrolled { .. })) {
+            state.action = Action::None;
+            return Status::Ignored;
+        }
+
         if let Some(status) = self.on_event_any_modifiers(state, &event, pos, shell) {
             return status;
         }
@@ -297,13 +360,34 @@ where
             state.bpm.set(bpm);
         }
 
+        if self.revision != state.revision.get() {
+            state.waveform_cache.borrow_mut().take();
+            state.revision.set(self.revision);
+        }
+
+        let resizing = state
+            .resized_at
+            .get()
+            .is_some_and(|resized_at| resized_at.elapsed() < RESIZE_DEBOUNCE);
+
         if state
             .last_bounds
             .get()
             .is_none_or(|last_bounds| last_bounds != bounds)
         {
-            state.waveform_cache.borrow_mut().take();
+            // rebuilding the waveforms on every frame of a resize stutters, so keep drawing the old ones
+            if resizing && state.waveform_cache.borrow().is_some() {
+                state.stale_size.set(true);
+            } else {
+                state.waveform_cache.borrow_mut().take();
+            }
             state.last_bounds.set(Some(layout.bounds()));
+        } else if state.stale_size.get() && !resizing {
+            state.waveform_cache.borrow_mut().take();
+        }
+
+        if state.waveform_cache.borrow().is_none() {
+            state.stale_size.set(false);
         }
 
         if state
@@ -390,6 +474,65 @@ where
         Self {
             inner,
             tracks: RefCell::default(),
+            on_double_click: None,
+            on_delete: None,
+            overlap_policy: OverlapPolicy::default(),
+            tool: Tool::default(),
+            natural_scrolling: false,
+            scroll_sensitivity: 1.0,
+            editable: true,
+            revision: 0,
+        }
+    }
+
+    pub fn revision(mut self, revision: u64) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    pub fn natural_scrolling(mut self, natural_scrolling: bool) -> Self {
+        self.natural_scrolling = natural_scrolling;
+        self
+    }
+
+    pub fn scroll_sensitivity(mut self, scroll_sensitivity: f32) -> Self {
+        self.scroll_sensitivity = scroll_sensitivity;
+        self
+    }
+
+    pub fn tool(mut self, tool: Tool) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    pub fn overlap_policy(mut self, overlap_policy: OverlapPolicy) -> Self {
+        self.overlap_policy = overlap_policy;
+        self
+    }
+
+    pub fn on_double_click(
+        mut self,
+        on_double_click: impl Fn(Arc<TrackClip>) -> Message + 'a,
+    ) -> Self {
+        self.on_double_click = Some(Box::new(on_double_click));
+        self
+    }
+
+    pub fn on_delete(mut self, on_delete: impl Fn(Arc<TrackClip>) -> Message + 'a) -> Self {
+        self.on_delete = Some(Box::new(on_delete));
+        self
+    }
+
+    fn delete(&self, index: usize, clip: Arc<TrackClip>, shell: &mut Shell<'_, Message>) {
+        self.inner.tracks.read().unwrap()[index].remove_clip(&clip);
+
+        if let Some(on_delete) = &self.on_delete {
+            shell.publish(on_delete(clip));
         }
     }
 
@@ -595,6 +738,23 @@ where
         if let Event::Mouse(event) = event {
             match event {
                 mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                    if let Action::DraggingClip(clip, index, _) = &state.action {
+                        // a plain click shouldn't change the clips underneath
+                        if state.drag_origin.take() != Some((*index, clip.get_global_start())) {
+                            let removed = self.inner.tracks.read().unwrap()[*index]
+                                .resolve_overlaps(clip, self.overlap_policy);
+
+                            if let Some(on_delete) = &self.on_delete {
+                                for clip in removed {
+                                    shell.publish(on_delete(clip));
+                                }
+                            }
+
+                            state.waveform_cache.borrow_mut().take();
+                            shell.invalidate_layout();
+                        }
+                    }
+
                     state.action = Action::None;
                     return Some(Status::Captured);
                 }
@@ -664,7 +824,7 @@ where
                                     .get_clip_at_global_time(&self.inner.meter, time);
 
                                 if let Some(clip) = clip {
-                                    self.inner.tracks.read().unwrap()[index].remove_clip(&clip);
+                                    self.delete(index, clip, shell);
 
                                     state.waveform_cache.borrow_mut().take();
                                     shell.invalidate_layout();
@@ -767,35 +927,66 @@ where
                     return Some(Status::Captured);
                 }
                 mouse::Event::ButtonPressed(button) => match button {
-                    mouse::Button::Left => {
+                    // the ruler always moves the playhead, regardless of the tool
+                    mouse::Button::Left if cursor.y < LINE_HEIGHT => {
                         if let Some(status) = self.lmb_none_or_alt(state, cursor) {
                             return Some(status);
                         }
                     }
-                    mouse::Button::Right => {
-                        if cursor.y > LINE_HEIGHT {
-                            let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
-                            if index < self.inner.tracks.read().unwrap().len() {
-                                let time = cursor
-                                    .x
-                                    .mul_add(state.scale.x.get().exp2(), state.position.x.get())
-                                    as usize;
-
-                                let clip = state.tracks.borrow()[index]
-                                    .get_clip_at_global_time(&self.inner.meter, time);
-
-                                if let Some(clip) = clip {
-                                    self.inner.tracks.read().unwrap()[index].remove_clip(&clip);
-
-                                    state.waveform_cache.borrow_mut().take();
-                                    shell.invalidate_layout();
+                    mouse::Button::Left => match self.tool {
+                        Tool::Select => {
+                            let click =
+                                mouse::Click::new(cursor, mouse::Button::Left, state.last_click);
+                            state.last_click = Some(click);
+
+                            if matches!(click.kind(), mouse::click::Kind::Double) {
+                                if let Some(status) = self.double_click(state, cursor, shell) {
+                                    return Some(status);
+                                }
+                            }
 
-                                    state.action = Action::DeletingClips;
+                            if let Some(status) = self.lmb_none_or_alt(state, cursor) {
+                                return Some(status);
+                            }
+                        }
+                        Tool::Draw => {
+                            if let Some(status) = self.lmb_none_or_alt(state, cursor) {
+                                return Some(status);
+                            }
 
-                                    return Some(Status::Captured);
-                                }
+                            if let Some(status) = self.draw(state, cursor, shell) {
+                                return Some(status);
+                            }
+                        }
+                        Tool::Erase => {
+                            if let Some(status) = self.start_deleting(state, cursor, shell) {
+                                return Some(status);
                             }
                         }
+                        Tool::Split => {
+                            if let Some(status) = self.split(state, cursor, shell) {
+                                return Some(status);
+                            }
+                        }
+                        Tool::Mute => {
+                            if let Some(clip) = self.clip_at(state, cursor) {
+                                clip.toggle_mute();
+                                return Some(Status::Captured);
+                            }
+                        }
+                        Tool::Zoom => {
+                            self.zoom(state, cursor, -1.0, shell);
+                            return Some(Status::Captured);
+                        }
+                    },
+                    mouse::Button::Right if self.tool == Tool::Zoom => {
+                        self.zoom(state, cursor, 1.0, shell);
+                        return Some(Status::Captured);
+                    }
+                    mouse::Button::Right => {
+                        if let Some(status) = self.start_deleting(state, cursor, shell) {
+                            return Some(status);
+                        }
                     }
                     _ => {}
                 },
@@ -815,24 +1006,12 @@ where
         if let Event::Mouse(event) = event {
             match event {
                 mouse::Event::WheelScrolled { delta } => {
-                    let x = match delta {
+                    let diff = match delta {
                         ScrollDelta::Pixels { x: _, y } => -y * 0.01,
                         ScrollDelta::Lines { x: _, y } => -y * 0.5,
                     };
 
-                    let x = (x + state.scale.x.get()).clamp(3.0, 12.999_999);
-
-                    let cursor_content_x = cursor
-                        .x
-                        .mul_add(state.scale.x.get().exp2(), state.position.x.get());
-
-                    state
-                        .position
-                        .x
-                        .set(cursor.x.mul_add(-x.exp2(), cursor_content_x).max(0.0));
-                    state.scale.x.set(x);
-                    state.waveform_cache.borrow_mut().take();
-                    shell.invalidate_layout();
+                    self.zoom(state, cursor, diff, shell);
 
                     return Some(Status::Captured);
                 }
@@ -862,6 +1041,8 @@ where
                                     self.inner.tracks.read().unwrap()[index].try_push(&clip)
                                 );
 
+                                state.drag_origin = Some((index, clip.get_global_start()));
+                                state.last_clip = Some(clip.clone());
                                 state.action = Action::DraggingClip(clip, index, offset);
 
                                 return Some(Status::Captured);
@@ -934,6 +1115,210 @@ where
         None
     }
 
+    /// applies the scroll sensitivity and direction to `delta`
+    fn adjust_scroll(&self, delta: ScrollDelta) -> ScrollDelta {
+        let factor = if self.natural_scrolling {
+            -self.scroll_sensitivity
+        } else {
+            self.scroll_sensitivity
+        };
+
+        match delta {
+            ScrollDelta::Lines { x, y } => ScrollDelta::Lines {
+                x: x * factor,
+                y: y * factor,
+            },
+            ScrollDelta::Pixels { x, y } => ScrollDelta::Pixels {
+                x: x * factor,
+                y: y * factor,
+            },
+        }
+    }
+
+    /// places a copy of the last dragged clip at the cursor, and starts dragging it
+    fn draw(
+        &self,
+        state: &mut State<'_, Message>,
+        cursor: Point,
+        shell: &mut Shell<'_, Message>,
+    ) -> Option<Status> {
+        let last_clip = state.last_clip.as_ref()?;
+
+        let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
+        if index >= self.inner.tracks.read().unwrap().len() {
+            return None;
+        }
+
+        let time = cursor
+            .x
+            .mul_add(state.scale.x.get().exp2(), state.position.x.get())
+            as usize;
+        let mut position = Position::from_interleaved_samples(time, &self.inner.meter);
+
+        if !state.modifiers.alt() {
+            position = position.snap(state.scale.x.get(), &self.inner.meter);
+        }
+
+        let clip = Arc::new((**last_clip).clone());
+        clip.move_to(position);
+
+        if !self.inner.tracks.read().unwrap()[index].try_push(&clip) {
+            return None;
+        }
+
+        let offset = (position.in_interleaved_samples(&self.inner.meter) as f32
+            - state.position.x.get())
+            / state.scale.x.get().exp2()
+            - cursor.x;
+
+        // the new clip always has its overlaps resolved when it's released
+        state.drag_origin = None;
+        state.last_clip = Some(clip.clone());
+        state.action = Action::DraggingClip(clip, index, offset);
+
+        state.waveform_cache.borrow_mut().take();
+        shell.invalidate_layout();
+
+        Some(Status::Captured)
+    }
+
+    /// zooms in horizontally by `diff` steps when it's negative, or out when it's positive,
+    /// keeping the time under the cursor in place
+    fn zoom(
+        &self,
+        state: &State<'_, Message>,
+        cursor: Point,
+        diff: f32,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let x = (diff + state.scale.x.get()).clamp(3.0, 12.999_999);
+
+        let cursor_content_x = cursor
+            .x
+            .mul_add(state.scale.x.get().exp2(), state.position.x.get());
+
+        state
+            .position
+            .x
+            .set(cursor.x.mul_add(-x.exp2(), cursor_content_x).max(0.0));
+        state.scale.x.set(x);
+        state.waveform_cache.borrow_mut().take();
+        shell.invalidate_layout();
+    }
+
+    /// the clip under the cursor, if there is one
+    fn clip_at(&self, state: &State<'_, Message>, cursor: Point) -> Option<Arc<TrackClip>> {
+        if cursor.y <= LINE_HEIGHT {
+            return None;
+        }
+
+        let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
+        if index >= self.inner.tracks.read().unwrap().len() {
+            return None;
+        }
+
+        let time = cursor
+            .x
+            .mul_add(state.scale.x.get().exp2(), state.position.x.get())
+            as usize;
+
+        state.tracks.borrow()[index].get_clip_at_global_time(&self.inner.meter, time)
+    }
+
+    fn start_deleting(
+        &self,
+        state: &mut State<'_, Message>,
+        cursor: Point,
+        shell: &mut Shell<'_, Message>,
+    ) -> Option<Status> {
+        if cursor.y > LINE_HEIGHT {
+            let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
+            if index < self.inner.tracks.read().unwrap().len() {
+                let time = cursor
+                    .x
+                    .mul_add(state.scale.x.get().exp2(), state.position.x.get())
+                    as usize;
+
+                let clip =
+                    state.tracks.borrow()[index].get_clip_at_global_time(&self.inner.meter, time);
+
+                if let Some(clip) = clip {
+                    self.delete(index, clip, shell);
+
+                    state.waveform_cache.borrow_mut().take();
+                    shell.invalidate_layout();
+
+                    state.action = Action::DeletingClips;
+
+                    return Some(Status::Captured);
+                }
+            }
+        }
+        None
+    }
+
+    fn split(
+        &self,
+        state: &State<'_, Message>,
+        cursor: Point,
+        shell: &mut Shell<'_, Message>,
+    ) -> Option<Status> {
+        if cursor.y > LINE_HEIGHT {
+            let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
+            if index < self.inner.tracks.read().unwrap().len() {
+                let time = cursor
+                    .x
+                    .mul_add(state.scale.x.get().exp2(), state.position.x.get())
+                    as usize;
+
+                let clip =
+                    state.tracks.borrow()[index].get_clip_at_global_time(&self.inner.meter, time);
+
+                if let Some(clip) = clip {
+                    let position = Position::from_interleaved_samples(time, &self.inner.meter)
+                        .snap(state.scale.x.get(), &self.inner.meter);
+
+                    self.inner.tracks.read().unwrap()[index].split_clip(&clip, position);
+
+                    state.waveform_cache.borrow_mut().take();
+                    shell.invalidate_layout();
+
+                    return Some(Status::Captured);
+                }
+            }
+        }
+        None
+    }
+
+    fn double_click(
+        &self,
+        state: &State<'_, Message>,
+        cursor: Point,
+        shell: &mut Shell<'_, Message>,
+    ) -> Option<Status> {
+        let on_double_click = self.on_double_click.as_ref()?;
+
+        if cursor.y > LINE_HEIGHT {
+            let index = ((cursor.y - LINE_HEIGHT) / state.scale.y.get()) as usize;
+            if index < self.inner.tracks.read().unwrap().len() {
+                let time = cursor
+                    .x
+                    .mul_add(state.scale.x.get().exp2(), state.position.x.get())
+                    as usize;
+
+                let clip =
+                    state.tracks.borrow()[index].get_clip_at_global_time(&self.inner.meter, time);
+
+                if let Some(clip) = clip {
+                    shell.publish(on_double_click(clip));
+
+                    return Some(Status::Captured);
+                }
+            }
+        }
+        None
+    }
+
     fn lmb_none_or_alt(&self, state: &mut State<'_, Message>, cursor: Point) -> Option<Status> {
         if cursor.y < LINE_HEIGHT {
             let mut time = Position::from_interleaved_samples(
@@ -1007,6 +1392,8 @@ where
                         state.action = Action::ClipTrimmingEnd(clip, offset + pixel_len);
                     }
                     (false, false) => {
+                        state.drag_origin = Some((index, clip.get_global_start()));
+                        state.last_clip = Some(clip.clone());
                         state.action = Action::DraggingClip(clip, index, offset);
                     }
                 }
@@ -2,6 +2,8 @@ mod arrangement;
 mod arrangement_position;
 mod arrangement_scale;
 mod mesh_ext;
+mod stereo_meter;
+mod tool;
 mod track;
 mod track_clip;
 mod vsplit;
@@ -10,6 +12,8 @@ pub use arrangement::Arrangement;
 pub use arrangement_position::ArrangementPosition;
 pub use arrangement_scale::ArrangementScale;
 pub use mesh_ext::MeshExt;
+pub use stereo_meter::StereoMeter;
+pub use tool::Tool;
 pub use track::Track;
 pub use track_clip::TrackClip;
 pub use vsplit::VSplit;
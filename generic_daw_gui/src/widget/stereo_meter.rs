@@ -0,0 +1,180 @@
+use generic_daw_core::Arrangement as ArrangementInner;
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        widget::Tree,
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+    },
+    event::Status,
+    mouse::Cursor,
+    window, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme,
+};
+use std::{
+    f32::consts::FRAC_1_SQRT_2,
+    fmt::{Debug, Formatter},
+    sync::{atomic::Ordering::SeqCst, Arc},
+};
+
+/// the height of the correlation bar
+const BAR_HEIGHT: f32 = 8.0;
+/// the space between the goniometer and the correlation bar
+const SPACING: f32 = 5.0;
+/// the size of a single goniometer point
+const POINT_SIZE: f32 = 1.5;
+
+/// a goniometer of the live output, with a correlation meter below it
+pub struct StereoMeter {
+    inner: Arc<ArrangementInner>,
+}
+
+impl Debug for StereoMeter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StereoMeter").finish_non_exhaustive()
+    }
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for StereoMeter {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Shrink)
+    }
+
+    fn layout(&self, _: &mut Tree, _: &Renderer, limits: &Limits) -> Node {
+        let width = limits.max().width.min(200.0);
+
+        Node::new(Size::new(width, width + SPACING + BAR_HEIGHT))
+    }
+
+    fn on_event(
+        &mut self,
+        _: &mut Tree,
+        event: Event,
+        _: Layout<'_>,
+        _: Cursor,
+        _: &Renderer,
+        _: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _: &Rectangle,
+    ) -> Status {
+        if let Event::Window(window::Event::RedrawRequested(..)) = event {
+            if self.inner.meter.playing.load(SeqCst) || !self.inner.tap.is_silent() {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+        }
+
+        Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        _: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _: &Style,
+        layout: Layout<'_>,
+        _: Cursor,
+        _: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme.extended_palette();
+
+        let scope = Rectangle::new(bounds.position(), Size::new(bounds.width, bounds.width));
+        let center = scope.center();
+        let radius = scope.width * 0.5;
+
+        renderer.fill_quad(
+            Quad {
+                bounds: scope,
+                ..Quad::default()
+            },
+            palette.background.weak.color,
+        );
+
+        // the mid axis and the side axis
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(Point::new(center.x, scope.y), Size::new(1.0, scope.height)),
+                ..Quad::default()
+            },
+            palette.background.strong.color,
+        );
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(Point::new(scope.x, center.y), Size::new(scope.width, 1.0)),
+                ..Quad::default()
+            },
+            palette.background.strong.color,
+        );
+
+        // rotated by 45 degrees, so that mono points straight up and out of phase sideways
+        for [l, r] in self.inner.tap.frames() {
+            let side = ((r - l) * FRAC_1_SQRT_2).clamp(-1.0, 1.0);
+            let mid = ((l + r) * FRAC_1_SQRT_2).clamp(-1.0, 1.0);
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: Rectangle::new(
+                        Point::new(
+                            side.mul_add(radius, center.x),
+                            mid.mul_add(-radius, center.y),
+                        ),
+                        Size::new(POINT_SIZE, POINT_SIZE),
+                    ),
+                    ..Quad::default()
+                },
+                palette.primary.base.color,
+            );
+        }
+
+        let bar = Rectangle::new(
+            Point::new(bounds.x, scope.y + scope.height + SPACING),
+            Size::new(bounds.width, BAR_HEIGHT),
+        );
+
+        renderer.fill_quad(
+            Quad {
+                bounds: bar,
+                ..Quad::default()
+            },
+            palette.background.weak.color,
+        );
+
+        if let Some(correlation) = self.inner.tap.correlation() {
+            let middle = bar.center_x();
+            let end = correlation.mul_add(bar.width * 0.5, middle);
+
+            // out of phase material is highlighted, since it cancels out when summed to mono
+            let color = if correlation < 0.0 {
+                palette.danger.base.color
+            } else {
+                palette.success.base.color
+            };
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: Rectangle::new(
+                        Point::new(middle.min(end), bar.y),
+                        Size::new((end - middle).abs().max(1.0), bar.height),
+                    ),
+                    ..Quad::default()
+                },
+                color,
+            );
+        }
+    }
+}
+
+impl StereoMeter {
+    pub const fn new(inner: Arc<ArrangementInner>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, Message> From<StereoMeter> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+{
+    fn from(value: StereoMeter) -> Self {
+        Self::new(value)
+    }
+}
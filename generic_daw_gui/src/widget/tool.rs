@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter};
+use strum::VariantArray;
+
+/// what a plain left click does in the arrangement
+///
+/// modifier shortcuts keep working regardless of the tool
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, VariantArray)]
+pub enum Tool {
+    /// move, trim and double click clips
+    #[default]
+    Select,
+    /// place copies of the last clip that was moved, and move clips like the select tool
+    Draw,
+    /// delete clips
+    Erase,
+    /// split clips in two
+    Split,
+    /// mute and unmute clips
+    Mute,
+    /// zoom in with the left mouse button, and out with the right one
+    Zoom,
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Select => "Select",
+            Self::Draw => "Draw",
+            Self::Erase => "Erase",
+            Self::Split => "Split",
+            Self::Mute => "Mute",
+            Self::Zoom => "Zoom",
+        })
+    }
+}
+
+impl Tool {
+    /// the tool after this one, wrapping around
+    #[must_use]
+    pub fn next(self) -> Self {
+        let index = Self::VARIANTS
+            .iter()
+            .position(|tool| *tool == self)
+            .unwrap();
+        Self::VARIANTS[(index + 1) % Self::VARIANTS.len()]
+    }
+}
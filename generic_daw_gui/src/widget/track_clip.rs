@@ -76,6 +76,9 @@ impl<Message> Widget<Message, Theme, Renderer> for TrackClip {
             ..Quad::default()
         };
 
+        // muted clips are drawn faded out
+        let alpha = if self.inner.is_muted() { 0.5 } else { 1.0 };
+
         renderer.fill_quad(
             clip_background,
             theme
@@ -83,7 +86,7 @@ impl<Message> Widget<Message, Theme, Renderer> for TrackClip {
                 .primary
                 .weak
                 .color
-                .scale_alpha(0.25),
+                .scale_alpha(0.25 * alpha),
         );
 
         // height of the clip, excluding the text, clipped off by the top of the arrangement
@@ -100,11 +103,27 @@ impl<Message> Widget<Message, Theme, Renderer> for TrackClip {
             ..Quad::default()
         };
 
-        renderer.fill_quad(text_background, theme.extended_palette().primary.weak.color);
+        renderer.fill_quad(
+            text_background,
+            theme
+                .extended_palette()
+                .primary
+                .weak
+                .color
+                .scale_alpha(alpha),
+        );
+
+        // the name of the sample, followed by its key when it was detected
+        let content = match &*self.inner {
+            TrackClipInner::Audio(clip) => clip.audio.key().map_or_else(
+                || self.inner.get_name(),
+                |key| format!("{} ({key})", self.inner.get_name()),
+            ),
+            TrackClipInner::Midi(..) => self.inner.get_name(),
+        };
 
-        // the text containing the name of the sample
         let text = Text {
-            content: self.inner.get_name(),
+            content,
             bounds: Size::new(f32::INFINITY, 0.0),
             size: renderer.default_size(),
             line_height: LineHeight::default(),